@@ -0,0 +1,96 @@
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader},
+    path::PathBuf,
+    process,
+};
+
+use rusty_rattle::views::{
+    export::{self, ExportOutput, Exporter},
+    Theme,
+};
+
+const USAGE: &str = "usage: rusty-rattle-export <snapshots> <output> \
+[--cell-size <pixels>] [--fps <frames>] [--theme <name>] [--frame <n>]
+
+renders the last game in a file of the snapshots a remote client received
+as game.gif, a directory of svg frames, or game.svg for a single frame
+(the last one, or the one chosen with --frame)";
+
+fn main() {
+    if let Err(e) = run(env::args().skip(1).collect()) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> io::Result<()> {
+    let mut paths = Vec::new();
+    let mut cell_size = None;
+    let mut fps = None;
+    let mut theme = Theme::default();
+    let mut frame = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| invalid(format!("{name} needs a value\n\n{USAGE}")))
+        };
+        match arg.as_str() {
+            "--cell-size" => cell_size = Some(number(&value("--cell-size")?)?),
+            "--fps" => fps = Some(number(&value("--fps")?)?),
+            "--theme" => {
+                let name = value("--theme")?;
+                theme = Theme::named(&name)
+                    .map_err(invalid)?
+                    .ok_or_else(|| invalid(format!("no theme {name}")))?;
+            }
+            "--frame" => frame = Some(number(&value("--frame")?)?),
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ if arg.starts_with("--") => {
+                return Err(invalid(format!("unknown option {arg}\n\n{USAGE}")))
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    let [snapshots, output] =
+        <[PathBuf; 2]>::try_from(paths).map_err(|_| invalid(USAGE.to_string()))?;
+
+    let mut frames = export::replay_frames(BufReader::new(File::open(&snapshots)?))?;
+    if frames.is_empty() {
+        return Err(invalid(format!("no game in {}", snapshots.display())));
+    }
+    if let Some(frame) = frame {
+        if frame >= frames.len() {
+            return Err(invalid(format!(
+                "the game only has {} frames",
+                frames.len()
+            )));
+        }
+        frames.truncate(frame + 1);
+    }
+
+    let mut exporter = Exporter::new(ExportOutput::from_path(output));
+    if let Some(cell_size) = cell_size {
+        exporter = exporter.with_cell_size(cell_size);
+    }
+    if let Some(fps) = fps {
+        exporter = exporter.with_fps(fps as u32);
+    }
+    exporter.export(&frames, &theme)
+}
+
+fn number(value: &str) -> io::Result<usize> {
+    value
+        .parse()
+        .map_err(|e| invalid(format!("invalid number {value:?}: {e}")))
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
@@ -0,0 +1,153 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::{
+    keymap::Keymap,
+    locale::{self, Messages},
+    settings::{self, GRID_HEIGHT_RANGE, GRID_WIDTH_RANGE, LEVEL_RANGE, UPDATE_INTERVAL_MS_RANGE},
+    views::Theme,
+    ConfigBuilder,
+};
+
+pub const VERSION: &str = concat!("rusty-rattle ", env!("CARGO_PKG_VERSION"));
+
+//what the command line asks for
+pub enum Command {
+    Play(Box<ConfigBuilder>),
+    WriteConfig(Box<ConfigBuilder>),
+    Help,
+    Version,
+}
+
+//the flags are applied over the config, so they win over the environment;
+//values come after the flag or after an =, like --height 20 or --height=20
+pub fn parse(
+    args: impl IntoIterator<Item = String>,
+    mut config: ConfigBuilder,
+) -> Result<Command, String> {
+    let messages = config.locale().messages();
+    let mut args = args.into_iter();
+    let mut speed_flag = None;
+    let mut write_config = false;
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| locale::fill(messages.needs_value, &[("flag", &flag)]))
+        };
+        match flag {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "--height" => {
+                config =
+                    config.set_grid_height(number(messages, flag, &value()?, GRID_HEIGHT_RANGE)?)
+            }
+            "--width" => {
+                config = config.set_grid_width(number(messages, flag, &value()?, GRID_WIDTH_RANGE)?)
+            }
+            "--interval-ms" | "--level" => {
+                if let Some(other) = speed_flag.replace(flag.to_string()) {
+                    return Err(locale::fill(
+                        messages.speed_set_twice,
+                        &[("other", &other), ("flag", &flag)],
+                    ));
+                }
+                let interval = match flag {
+                    "--level" => {
+                        settings::level_interval(number(messages, flag, &value()?, LEVEL_RANGE)?)
+                    }
+                    _ => Duration::from_millis(number(
+                        messages,
+                        flag,
+                        &value()?,
+                        UPDATE_INTERVAL_MS_RANGE,
+                    )? as u64),
+                };
+                config = config.set_update_interval(interval);
+            }
+            "--seed" => {
+                let seed = value()?;
+                let seed = seed.parse().map_err(|e| {
+                    locale::fill(
+                        messages.invalid_seed,
+                        &[("seed", &format!("{seed:?}")), ("error", &e)],
+                    )
+                })?;
+                config = config.set_seed(seed);
+            }
+            "--view" => {
+                let views = value()?;
+                config = config.clear_mirror_views();
+                for (i, view_type) in views.split(',').enumerate() {
+                    let view_type = view_type.trim().parse()?;
+                    config = match i {
+                        0 => config.set_view_type(view_type),
+                        _ => config.add_mirror_view(view_type),
+                    };
+                }
+            }
+            "--mode" => config = config.set_mode(value()?.parse()?),
+            "--theme" => {
+                let name = value()?;
+                let theme = Theme::named(&name)?.ok_or_else(|| {
+                    let names: Vec<_> = Theme::available()
+                        .iter()
+                        .map(|theme| theme.name().to_string())
+                        .collect();
+                    locale::fill(
+                        messages.no_theme,
+                        &[("name", &format!("{name:?}")), ("names", &names.join(", "))],
+                    )
+                })?;
+                config = config.set_theme(theme);
+            }
+            "--keymap" => {
+                let path = PathBuf::from(value()?);
+                Keymap::check_file(&path)?;
+                config = config.set_keymap(path);
+            }
+            "--play" if inline.is_none() => config = config.set_play(true),
+            "--write-config" if inline.is_none() => write_config = true,
+            _ => {
+                return Err(locale::fill(
+                    messages.unknown_argument,
+                    &[("argument", &format!("{arg:?}"))],
+                ))
+            }
+        }
+    }
+    match write_config {
+        true => Ok(Command::WriteConfig(Box::new(config))),
+        false => Ok(Command::Play(Box::new(config))),
+    }
+}
+
+fn number(
+    messages: &Messages,
+    flag: &str,
+    value: &str,
+    (min, max): (usize, usize),
+) -> Result<usize, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|n| (min..=max).contains(n))
+        .ok_or_else(|| {
+            locale::fill(
+                messages.not_in_range,
+                &[
+                    ("flag", &flag),
+                    ("min", &min),
+                    ("max", &max),
+                    ("value", &format!("{value:?}")),
+                ],
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,146 @@
+use std::{env, fs, process, time::Duration};
+
+use super::{parse, Command};
+use crate::{locale::Locale, settings::GameMode, Config, ViewType};
+
+fn parse_args(args: &[&str]) -> Result<Command, String> {
+    parse(args.iter().map(|arg| arg.to_string()), Config::builder())
+}
+
+fn config(args: &[&str]) -> Config {
+    match parse_args(args) {
+        Ok(Command::Play(config)) => (*config).build(),
+        Ok(_) => panic!("expected a game to play"),
+        Err(e) => panic!("{e}"),
+    }
+}
+
+#[test]
+fn test_flags_set_the_settings() {
+    let config = config(&[
+        "--height",
+        "12",
+        "--width=40",
+        "--interval-ms",
+        "150",
+        "--mode",
+        "wrap",
+        "--theme",
+        "monochrome",
+        "--seed",
+        "7",
+        "--play",
+    ]);
+    let settings = config.initial_settings(None);
+    assert_eq!(settings.grid_height, 12);
+    assert_eq!(settings.grid_width, 40);
+    assert_eq!(settings.update_interval, Duration::from_millis(150));
+    assert_eq!(settings.mode, GameMode::Wrap);
+    assert_eq!(settings.theme.name(), "monochrome");
+    assert_eq!(config.seed(), Some(7));
+    assert!(config.play());
+}
+
+#[test]
+fn test_flags_override_the_last_settings() {
+    let last = config(&["--height", "30", "--width", "50"]).initial_settings(None);
+    let settings = config(&["--height", "15"]).initial_settings(Some(last));
+    assert_eq!(settings.grid_height, 15);
+    assert_eq!(settings.grid_width, 50);
+
+    //without flags the title screen offers the defaults
+    let config = config(&[]);
+    assert_eq!(config.initial_settings(None).grid_height, 20);
+    assert!(!config.play());
+}
+
+#[test]
+fn test_level_sets_the_speed() {
+    let interval = |level| {
+        config(&["--level", level])
+            .initial_settings(None)
+            .update_interval
+    };
+    assert_eq!(interval("1"), Duration::from_millis(300));
+    assert_eq!(interval("10"), Duration::from_millis(30));
+    assert!(parse_args(&["--level", "11"]).is_err());
+    assert!(parse_args(&["--level", "3", "--interval-ms", "100"]).is_err());
+}
+
+#[test]
+fn test_view_flag_replaces_the_views() {
+    let config = parse(
+        ["--view".to_string(), "web, narrated".to_string()],
+        Config::builder().add_mirror_view(ViewType::GUI),
+    );
+    let Ok(Command::Play(config)) = config else {
+        panic!("expected a game to play");
+    };
+    let config = (*config).build();
+    assert_eq!(*config.view_type(), ViewType::Web);
+    assert_eq!(config.mirror_views(), [ViewType::Narrated]);
+}
+
+#[test]
+fn test_invalid_arguments() {
+    for args in [
+        &["--height", "5"][..],
+        &["--width", "wide"],
+        &["--height"],
+        &["--mode", "spiral"],
+        &["--view", "tui,vr"],
+        &["--theme", "no-such-theme"],
+        &["--seed", "-1"],
+        &["--play=yes"],
+        &["--fast"],
+        &["20"],
+    ] {
+        assert!(parse_args(args).is_err(), "{args:?} should be rejected");
+    }
+}
+
+#[test]
+fn test_keymap_flag_checks_the_file() {
+    let path = env::temp_dir().join(format!("rusty-rattle-keymap-{}.toml", process::id()));
+    fs::write(&path, "pause = \"space\"\n").unwrap();
+    let keymap = path.to_string_lossy().to_string();
+    assert_eq!(config(&["--keymap", &keymap]).keymap(), Some(&path));
+
+    fs::write(&path, "pause = [").unwrap();
+    assert!(parse_args(&["--keymap", &keymap]).is_err());
+    //one of the two actions could never be used
+    fs::write(&path, "pause = \"w\"\n").unwrap();
+    assert!(parse_args(&["--keymap", &keymap]).is_err());
+    fs::remove_file(&path).unwrap();
+
+    //a file that does not exist yet is created when a key is bound
+    assert!(parse_args(&["--keymap", &keymap]).is_ok());
+}
+
+#[test]
+fn test_help_and_version() {
+    assert!(matches!(parse_args(&["--help"]), Ok(Command::Help)));
+    assert!(matches!(
+        parse_args(&["--height", "12", "-V"]),
+        Ok(Command::Version)
+    ));
+}
+
+#[test]
+fn test_errors_in_the_players_language() {
+    let error = |locale| {
+        let args = ["--height".to_string(), "5".to_string()];
+        match parse(args, Config::builder().set_locale(locale)) {
+            Err(e) => e,
+            Ok(_) => panic!("a height of 5 should be rejected"),
+        }
+    };
+    assert_eq!(
+        error(Locale::English),
+        "--height must be a number between 8 and 100, not \"5\""
+    );
+    assert_eq!(
+        error(Locale::Italian),
+        "--height deve essere un numero tra 8 e 100, non \"5\""
+    );
+}
@@ -0,0 +1,343 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use toml::{Spanned, Value};
+
+use crate::{
+    keymap::Keymap,
+    settings::{
+        self, Settings, GRID_HEIGHT_RANGE, GRID_WIDTH_RANGE, LEVEL_RANGE, UPDATE_INTERVAL_MS_RANGE,
+    },
+    storage,
+    views::Theme,
+    Config, ConfigBuilder,
+};
+
+const CONFIG_FILE: &str = "config.toml";
+
+//a line of the config file that was skipped, and why
+#[derive(Debug, PartialEq)]
+pub struct Warning {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+//$XDG_CONFIG_HOME/rusty-rattle/config.toml
+pub fn path() -> Option<PathBuf> {
+    Some(storage::config_dir()?.join(CONFIG_FILE))
+}
+
+//the config file applied over the built-in defaults, a missing file changes
+//nothing and the lines that can't be used are reported without stopping the game
+pub fn load(config: ConfigBuilder) -> ConfigBuilder {
+    let Some(path) = path() else {
+        return config;
+    };
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return config,
+        Err(e) => {
+            eprintln!("failed to read {}: {e}", path.display());
+            return config;
+        }
+    };
+    let (config, warnings) = apply(&contents, config);
+    for warning in warnings {
+        eprintln!("{}: {warning}", path.display());
+    }
+    config
+}
+
+//a file that is not valid toml is skipped whole, otherwise only the keys
+//that are unknown or have invalid values
+pub fn apply(contents: &str, mut config: ConfigBuilder) -> (ConfigBuilder, Vec<Warning>) {
+    let line = |offset: usize| contents[..offset.min(contents.len())].matches('\n').count() + 1;
+    let table: BTreeMap<Spanned<String>, Spanned<Value>> = match toml::from_str(contents) {
+        Ok(table) => table,
+        Err(e) => {
+            let warning = Warning {
+                line: e.span().map_or(1, |span| line(span.start)),
+                message: e.message().to_string(),
+            };
+            return (config, vec![warning]);
+        }
+    };
+    let mut entries: Vec<_> = table.into_iter().collect();
+    entries.sort_by_key(|(key, _)| key.span().start);
+
+    let mut warnings = Vec::new();
+    let mut speed_key = None;
+    for (key, value) in entries {
+        let line = line(key.span().start);
+        let key = key.into_inner();
+        if matches!(key.as_str(), "interval_ms" | "level") {
+            if let Some(other) = speed_key.replace(key.clone()) {
+                let message = format!("{other} and {key} both set the speed, {key} is ignored");
+                warnings.push(Warning { line, message });
+                continue;
+            }
+        }
+        match apply_key(config.clone(), &key, value.get_ref()) {
+            Ok(applied) => config = applied,
+            Err(message) => warnings.push(Warning { line, message }),
+        }
+    }
+    (config, warnings)
+}
+
+fn apply_key(config: ConfigBuilder, key: &str, value: &Value) -> Result<ConfigBuilder, String> {
+    Ok(match key {
+        "height" => config.set_grid_height(number(key, value, GRID_HEIGHT_RANGE)?),
+        "width" => config.set_grid_width(number(key, value, GRID_WIDTH_RANGE)?),
+        "interval_ms" => config.set_update_interval(Duration::from_millis(number(
+            key,
+            value,
+            UPDATE_INTERVAL_MS_RANGE,
+        )? as u64)),
+        "level" => {
+            config.set_update_interval(settings::level_interval(number(key, value, LEVEL_RANGE)?))
+        }
+        "mode" => config.set_mode(string(key, value)?.parse()?),
+        "theme" => {
+            let name = string(key, value)?;
+            config.set_theme(Theme::named(name)?.ok_or_else(|| format!("no theme {name:?}"))?)
+        }
+        "seed" => config.set_seed(
+            value
+                .as_integer()
+                .and_then(|seed| u64::try_from(seed).ok())
+                .ok_or_else(|| format!("{key} should be a positive number"))?,
+        ),
+        //a list of views, or the same comma separated string as RUSTY_RATTLE_VIEW
+        "view" => {
+            let views: Vec<&str> = match value {
+                Value::String(views) => views.split(',').collect(),
+                Value::Array(views) => views
+                    .iter()
+                    .map(|view| string(key, view))
+                    .collect::<Result<_, _>>()?,
+                _ => return Err(format!("{key} should be a list of views")),
+            };
+            let mut config = config.clear_mirror_views();
+            for (i, view_type) in views.into_iter().enumerate() {
+                let view_type = view_type.trim().parse()?;
+                config = match i {
+                    0 => config.set_view_type(view_type),
+                    _ => config.add_mirror_view(view_type),
+                };
+            }
+            config
+        }
+        "keymap" => {
+            let path = PathBuf::from(string(key, value)?);
+            Keymap::check_file(&path)?;
+            config.set_keymap(path)
+        }
+        "glyphs" => config.set_glyphs(string(key, value)?.parse()?),
+        "render" => config.set_render_mode(string(key, value)?.parse()?),
+        "dead_zone" => {
+            let dead_zone = match value.as_array().map(Vec::as_slice) {
+                Some([across, down]) => {
+                    across
+                        .as_integer()
+                        .zip(down.as_integer())
+                        .and_then(|(across, down)| {
+                            Some((usize::try_from(across).ok()?, usize::try_from(down).ok()?))
+                        })
+                }
+                _ => None,
+            };
+            config.set_camera_dead_zone(
+                dead_zone.ok_or_else(|| format!("{key} should be two numbers like [5, 3]"))?,
+            )
+        }
+        "minimap" => config.set_minimap(
+            value
+                .as_bool()
+                .ok_or_else(|| format!("{key} should be true or false"))?,
+        ),
+        "web_addr" => {
+            let web_addr = string(key, value)?;
+            config.set_web_addr(
+                web_addr
+                    .parse()
+                    .map_err(|e| format!("invalid web address {web_addr:?}: {e}"))?,
+            )
+        }
+        "locale" => config.set_locale(string(key, value)?.parse()?),
+        "remote" => config.set_remote(string(key, value)?.parse()?),
+        _ => return Err(format!("unknown key {key:?}")),
+    })
+}
+
+fn number(key: &str, value: &Value, (min, max): (usize, usize)) -> Result<usize, String> {
+    value
+        .as_integer()
+        .and_then(|n| usize::try_from(n).ok())
+        .filter(|n| (min..=max).contains(n))
+        .ok_or_else(|| format!("{key} should be a number between {min} and {max}"))
+}
+
+fn string<'a>(key: &str, value: &'a Value) -> Result<&'a str, String> {
+    value
+        .as_str()
+        .ok_or_else(|| format!("{key} should be a string"))
+}
+
+//the config and the settings it starts with as a file to edit,
+//every key is explained and the unset ones are left commented out,
+//the settings of the last game included, so they don't pin every game after it
+pub fn to_toml(config: &Config, settings: &Settings) -> String {
+    let quote = |s: &str| Value::String(s.to_string()).to_string();
+    let optional = |key: &str, value: Option<String>, example: &str| match value {
+        Some(value) => format!("{key} = {value}"),
+        None => format!("# {key} = {example}"),
+    };
+
+    let views: Vec<_> = [config.view_type()]
+        .into_iter()
+        .chain(config.mirror_views())
+        .map(|view_type| quote(view_type.name()))
+        .collect();
+    let (across, down) = config.camera_dead_zone();
+
+    let mut toml = String::from(
+        "# rusty-rattle configuration\n\
+         # the RUSTY_RATTLE_* environment variables and the command line flags win over it\n",
+    );
+    writeln!(
+        toml,
+        "\n# the board of every game, commented out to start from the last one played\n{}\n{}",
+        optional(
+            "height",
+            config.grid_height.map(|height| height.to_string()),
+            &settings.grid_height.to_string()
+        ),
+        optional(
+            "width",
+            config.grid_width.map(|width| width.to_string()),
+            &settings.grid_width.to_string()
+        )
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# the step interval, 20 to 2000, or the speed as level = 1 (300ms) to 10 (30ms)\n{}",
+        optional(
+            "interval_ms",
+            config
+                .update_interval
+                .map(|interval| interval.as_millis().to_string()),
+            &settings.update_interval.as_millis().to_string()
+        )
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# classic or wrap\n{}",
+        optional(
+            "mode",
+            config.mode.map(|mode| quote(mode.name())),
+            &quote(settings.mode.name())
+        )
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# a preset or the name of a file in the themes directory\n{}",
+        optional(
+            "theme",
+            config.theme.as_ref().map(|theme| quote(theme.name())),
+            &quote(settings.theme.name())
+        )
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# places the apples the same way every time\n{}",
+        optional("seed", config.seed().map(|seed| seed.to_string()), "42")
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# tui, gui, web or narrated, the first one is played in\n\
+         # and the others show the same game\nview = [{}]",
+        views.join(", ")
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# the key bindings to use and save to\n{}",
+        optional(
+            "keymap",
+            config.keymap().map(|path| quote(&path.to_string_lossy())),
+            "\"keymap.toml\""
+        )
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# ascii, unicode or emoji\nglyphs = {}",
+        quote(config.glyphs().name())
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# cells, half-block or braille\nrender = {}",
+        quote(config.render_mode().name())
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# how close to the edges the head gets before a big board scrolls\n\
+         dead_zone = [{across}, {down}]"
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# shows the whole of a big board in a corner\nminimap = {}",
+        config.minimap()
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# where the web view serves its page\nweb_addr = {}",
+        quote(&config.web_addr().to_string())
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# the language, en or it\nlocale = {}",
+        quote(config.locale().code())
+    )
+    .unwrap();
+    writeln!(
+        toml,
+        "\n# where to accept remote control clients\n{}",
+        optional(
+            "remote",
+            config.remote().map(|remote| quote(&remote.to_string())),
+            "\"tcp:127.0.0.1:7777\""
+        )
+    )
+    .unwrap();
+    toml
+}
+
+pub fn save(path: &Path, config: &Config, settings: &Settings) -> io::Result<()> {
+    storage::write_atomic(path, to_toml(config, settings).as_bytes())
+}
+
+#[cfg(test)]
+mod tests;
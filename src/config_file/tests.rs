@@ -0,0 +1,193 @@
+use std::{env, fs, process, time::Duration};
+
+use super::{apply, to_toml, Warning};
+use crate::{
+    cli::{self, Command},
+    locale::Locale,
+    settings::{GameMode, Settings},
+    views::{glyphs::GlyphSet, tui_view::RenderMode},
+    Config, ViewType,
+};
+
+fn load(contents: &str) -> (Config, Vec<Warning>) {
+    let (config, warnings) = apply(contents, Config::builder());
+    (config.build(), warnings)
+}
+
+#[test]
+fn test_keys_set_the_config() {
+    let (config, warnings) = load(
+        r#"
+height = 12
+width = 40
+level = 10
+mode = "wrap"
+theme = "colorblind"
+seed = 7
+view = ["web", "narrated"]
+glyphs = "ascii"
+render = "braille"
+dead_zone = [2, 1]
+minimap = true
+web_addr = "0.0.0.0:9000"
+locale = "it"
+remote = "tcp:127.0.0.1:7777"
+"#,
+    );
+    assert_eq!(warnings, []);
+    let settings = config.initial_settings(None);
+    assert_eq!(settings.grid_height, 12);
+    assert_eq!(settings.grid_width, 40);
+    assert_eq!(settings.update_interval, Duration::from_millis(30));
+    assert_eq!(settings.mode, GameMode::Wrap);
+    assert_eq!(settings.theme.name(), "colorblind");
+    assert_eq!(config.seed(), Some(7));
+    assert_eq!(*config.view_type(), ViewType::Web);
+    assert_eq!(config.mirror_views(), [ViewType::Narrated]);
+    assert_eq!(config.glyphs(), GlyphSet::Ascii);
+    assert_eq!(config.render_mode(), RenderMode::Braille);
+    assert_eq!(config.camera_dead_zone(), (2, 1));
+    assert!(config.minimap());
+    assert_eq!(config.web_addr().port(), 9000);
+    assert_eq!(config.locale(), Locale::Italian);
+    assert!(config.remote().is_some());
+}
+
+#[test]
+fn test_unknown_and_invalid_keys_are_skipped() {
+    let (config, warnings) = load(
+        "height = 12\n\
+         colour = \"red\"\n\
+         \n\
+         width = 3\n\
+         mode = 1\n\
+         [board]\n\
+         size = 10\n",
+    );
+    let lines: Vec<_> = warnings.iter().map(|warning| warning.line).collect();
+    assert_eq!(lines, [2, 4, 5, 6]);
+    assert_eq!(warnings[0].message, "unknown key \"colour\"");
+    assert_eq!(
+        warnings[1].to_string(),
+        "line 4: width should be a number between 8 and 200"
+    );
+    //the valid keys are still used
+    assert_eq!(config.initial_settings(None).grid_height, 12);
+    assert_eq!(config.initial_settings(None).grid_width, 30);
+}
+
+#[test]
+fn test_invalid_toml_is_skipped_whole() {
+    let (config, warnings) = load("height = 12\nwidth = [\n");
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].line >= 2, "{:?}", warnings[0]);
+    assert_eq!(config.initial_settings(None).grid_height, 20);
+}
+
+#[test]
+fn test_only_one_speed_is_used() {
+    let (config, warnings) = load("interval_ms = 100\nlevel = 1\n");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].line, 2);
+    assert_eq!(
+        config.initial_settings(None).update_interval,
+        Duration::from_millis(100)
+    );
+}
+
+#[test]
+fn test_flags_win_over_the_file() {
+    let (config, _) = apply(
+        "height = 12\nwidth = 40\nview = \"tui,web\"\n",
+        Config::builder(),
+    );
+    let args = ["--height", "50", "--view", "narrated"].map(String::from);
+    let Ok(Command::Play(config)) = cli::parse(args, config) else {
+        panic!("expected a game to play");
+    };
+    let config = (*config).build();
+    let settings = config.initial_settings(None);
+    assert_eq!(settings.grid_height, 50);
+    assert_eq!(settings.grid_width, 40);
+    assert_eq!(*config.view_type(), ViewType::Narrated);
+    assert_eq!(config.mirror_views(), []);
+}
+
+#[test]
+fn test_written_file_reads_back_the_same() {
+    let (config, _) = load(
+        "height = 15\nlevel = 4\nmode = \"wrap\"\nview = [\"tui\", \"web\"]\n\
+         glyphs = \"emoji\"\nminimap = true\nlocale = \"it\"\n",
+    );
+    let settings = config.initial_settings(None);
+    let toml = to_toml(&config, &settings);
+
+    let (reread, warnings) = load(&toml);
+    assert_eq!(warnings, [], "{toml}");
+    assert_eq!(reread.initial_settings(None), settings);
+    assert_eq!(reread.view_type(), config.view_type());
+    assert_eq!(reread.mirror_views(), config.mirror_views());
+    assert_eq!(reread.glyphs(), GlyphSet::Emoji);
+    assert!(reread.minimap());
+    assert_eq!(reread.locale(), Locale::Italian);
+    //unset options are left as comments to fill in
+    assert!(toml.contains("\n# seed = 42\n"), "{toml}");
+    assert_eq!(reread.seed(), None);
+}
+
+#[test]
+fn test_default_file() {
+    let config = Config::builder().build();
+    let toml = to_toml(&config, &config.initial_settings(None));
+    assert!(toml.starts_with("# rusty-rattle configuration\n"));
+    assert!(toml.contains("\n# height = 20\n# width = 30\n"), "{toml}");
+    assert!(toml.contains("\n# interval_ms = 200\n"), "{toml}");
+    assert!(toml.contains("\nview = [\"tui\"]\n"), "{toml}");
+    assert!(
+        toml.contains("\n# remote = \"tcp:127.0.0.1:7777\"\n"),
+        "{toml}"
+    );
+}
+
+#[test]
+fn test_last_game_is_not_pinned_by_the_written_file() {
+    let config = Config::builder().set_grid_height(25).build();
+    let last = Settings {
+        grid_width: 50,
+        mode: GameMode::Wrap,
+        ..Settings::default()
+    };
+    let toml = to_toml(&config, &config.initial_settings(Some(last)));
+    assert!(toml.contains("\nheight = 25\n# width = 50\n"), "{toml}");
+    assert!(toml.contains("\n# mode = \"wrap\"\n"), "{toml}");
+
+    //the game after the next one still starts from the one before it
+    let (reread, _) = load(&toml);
+    let next = Settings {
+        grid_width: 60,
+        ..Settings::default()
+    };
+    let settings = reread.initial_settings(Some(next));
+    assert_eq!((settings.grid_height, settings.grid_width), (25, 60));
+    assert_eq!(settings.mode, GameMode::Classic);
+}
+
+#[test]
+fn test_keymap_file_is_checked() {
+    let path = env::temp_dir().join(format!("rusty-rattle-config-keymap-{}.toml", process::id()));
+    fs::write(&path, "pause = \"w\"\n").unwrap();
+    let (config, warnings) = load(&format!("keymap = {:?}\n", path.to_string_lossy()));
+    assert_eq!(warnings.len(), 1);
+    assert!(
+        warnings[0].message.starts_with("invalid keymap"),
+        "{:?}",
+        warnings[0]
+    );
+    assert_eq!(config.keymap(), None);
+
+    fs::write(&path, "pause = \"space\"\n").unwrap();
+    let (config, warnings) = load(&format!("keymap = {:?}\n", path.to_string_lossy()));
+    assert_eq!(warnings, []);
+    assert_eq!(config.keymap(), Some(&path));
+    fs::remove_file(&path).unwrap();
+}
@@ -1,41 +1,178 @@
-use std::{collections::VecDeque, sync::Mutex};
+use std::{
+    collections::VecDeque,
+    sync::{mpsc::Sender, Mutex},
+    time::Instant,
+};
 
 use crossterm::event::KeyCode;
 
-use crate::model::{GameState, Model, Orientation};
+use crate::{
+    event_processes::TickInterval,
+    keymap::KeyAction,
+    model::{title_menu::MenuInput, GameState, Model, Orientation},
+};
+
+//what the player can ask the game to do, whatever the input source
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Turn(Orientation),
+    Menu(MenuInput),
+    Start,
+    Pause,
+    OpenMenu,
+    //binds the key to the action selected in the controls screen
+    Bind(KeyCode),
+    //a letter or digit of the initials of a new high score
+    TypeInitial(char),
+    Exit,
+    ToggleLatencyOverlay,
+    ToggleRecording,
+}
 
 pub struct Controller {
     model: Model,
-    direction_buffer: Mutex<VecDeque<Orientation>>,
+    //every direction keeps the instant its key was read, to measure input latency
+    direction_buffer: Mutex<VecDeque<(Orientation, Instant)>>,
+    //remote clients receiving a snapshot of the game after each tick
+    subscribers: Vec<Sender<String>>,
+    tick_interval: TickInterval,
 }
 
 impl Controller {
-    pub fn new(model: Model) -> Self {
+    pub fn new(model: Model, tick_interval: TickInterval) -> Self {
         Controller {
             model,
             direction_buffer: Mutex::new(VecDeque::new()),
+            subscribers: Vec::new(),
+            tick_interval,
         }
     }
 
     pub fn update_model(&mut self) {
-        let direction = self.direction_buffer.lock().unwrap().pop_front();
+        let input = self.direction_buffer.lock().unwrap().pop_front();
         if self.model.game_state() == GameState::Playing {
-            self.model.update(direction);
+            self.model.update(input);
         }
+        self.send_snapshot();
+    }
+
+    pub fn animate(&mut self) {
+        self.model.animate();
     }
 
-    pub fn handle_input(&mut self, key_code: KeyCode) {
-        match key_code {
-            KeyCode::Enter => self.start_game(),
-            KeyCode::Char('a') => self.input_direction(Orientation::Left),
-            KeyCode::Char('w') => self.input_direction(Orientation::Up),
-            KeyCode::Char('d') => self.input_direction(Orientation::Right),
-            KeyCode::Char('s') => self.input_direction(Orientation::Down),
-            _ => {}
+    pub fn handle_input(&mut self, key_code: KeyCode, read_at: Instant) {
+        //the controls screen is waiting for any key to bind
+        if self.model.is_capturing_key() {
+            self.handle_action(Action::Bind(key_code), read_at);
+            return;
+        }
+
+        let key_action = self.model.keymap().action_for(key_code);
+
+        //letters type the initials of a new high score instead of their actions
+        if self.model.is_entering_initials() {
+            let action = match (key_action, key_code) {
+                (_, KeyCode::Char(c)) if c.is_ascii_alphanumeric() => Action::TypeInitial(c),
+                (Some(KeyAction::Confirm), _) | (_, KeyCode::Enter) => {
+                    Action::Menu(MenuInput::Select)
+                }
+                (Some(KeyAction::Menu), _) => Action::Menu(MenuInput::Back),
+                (_, KeyCode::Backspace) => Action::Menu(MenuInput::Backspace),
+                _ => return,
+            };
+            self.handle_action(action, read_at);
+            return;
+        }
+
+        let action = match (self.model.game_state(), key_action) {
+            (_, Some(KeyAction::LatencyOverlay)) => Action::ToggleLatencyOverlay,
+            (_, Some(KeyAction::Record)) => Action::ToggleRecording,
+            (
+                GameState::TitleScreen
+                | GameState::InGameMenu
+                | GameState::Settings
+                | GameState::Controls
+                | GameState::HighScores
+                | GameState::Stats
+                | GameState::ResumeOffer,
+                _,
+            ) => match Self::menu_input(key_action, key_code) {
+                Some(menu_input) => Action::Menu(menu_input),
+                None => return,
+            },
+            (GameState::GameOver, Some(KeyAction::Confirm)) => Action::Start,
+            (GameState::GameOver, Some(KeyAction::Menu)) => Action::Exit,
+            (_, Some(KeyAction::Menu)) => Action::OpenMenu,
+            (_, Some(KeyAction::Pause)) => Action::Pause,
+            (_, Some(KeyAction::Left)) => Action::Turn(Orientation::Left),
+            (_, Some(KeyAction::Up)) => Action::Turn(Orientation::Up),
+            (_, Some(KeyAction::Right)) => Action::Turn(Orientation::Right),
+            (_, Some(KeyAction::Down)) => Action::Turn(Orientation::Down),
+            _ => return,
+        };
+        self.handle_action(action, read_at);
+    }
+
+    //menus can always be navigated with the arrow keys, besides the bound keys
+    fn menu_input(key_action: Option<KeyAction>, key_code: KeyCode) -> Option<MenuInput> {
+        match (key_action, key_code) {
+            (Some(KeyAction::Up), _) | (_, KeyCode::Up) => Some(MenuInput::Up),
+            (Some(KeyAction::Down), _) | (_, KeyCode::Down) => Some(MenuInput::Down),
+            (Some(KeyAction::Left), _) | (_, KeyCode::Left) => Some(MenuInput::Left),
+            (Some(KeyAction::Right), _) | (_, KeyCode::Right) => Some(MenuInput::Right),
+            (Some(KeyAction::Confirm), _) => Some(MenuInput::Select),
+            (Some(KeyAction::Menu), _) => Some(MenuInput::Back),
+            (_, KeyCode::Backspace) => Some(MenuInput::Backspace),
+            (_, KeyCode::Char(c)) => c.to_digit(10).map(MenuInput::Digit),
+            _ => None,
         }
     }
 
-    fn input_direction(&mut self, direction: crate::model::Orientation) {
+    pub fn handle_action(&mut self, action: Action, read_at: Instant) {
+        match action {
+            Action::Turn(direction) => self.input_direction(direction, read_at),
+            Action::Menu(menu_input) => self.model.menu_input(menu_input),
+            Action::Start => self.start_game(),
+            Action::Pause => self.model.toggle_pause(),
+            Action::OpenMenu => self.model.open_menu(),
+            Action::Bind(key_code) => self.model.bind_key(key_code),
+            Action::TypeInitial(c) => self.model.type_initial(c),
+            Action::Exit => self.model.exit(),
+            Action::ToggleLatencyOverlay => self.model.toggle_latency_overlay(),
+            Action::ToggleRecording => self.model.toggle_recording(),
+        }
+        //a game may have been started from a menu with a different speed
+        self.tick_interval
+            .set(self.model.settings().update_interval);
+    }
+
+    //the player chose to close the game from a menu
+    pub fn has_exited(&self) -> bool {
+        self.model.game_state() == GameState::Exited
+    }
+
+    pub fn subscribe(&mut self, subscriber: Sender<String>) {
+        self.subscribers.push(subscriber);
+    }
+
+    //a game still in progress is saved, also when a remote client quits it
+    pub fn quit(&mut self) {
+        self.model.save_game();
+        self.model.dump_latency();
+    }
+
+    fn send_snapshot(&mut self) {
+        if self.subscribers.is_empty() {
+            return;
+        }
+        let snapshot =
+            serde_json::to_string(&self.model.snapshot()).expect("snapshot should serialize");
+        //forget the clients that disconnected
+        self.subscribers
+            .retain(|subscriber| subscriber.send(snapshot.clone()).is_ok());
+    }
+
+    fn input_direction(&mut self, direction: crate::model::Orientation, read_at: Instant) {
         match self.model.game_state() {
             GameState::Playing => {
                 let mut input_stack = self.direction_buffer.lock().unwrap();
@@ -43,20 +180,40 @@ impl Controller {
                 //(to prevent an issue with repeating keys by holding them down)
                 if input_stack
                     .back()
-                    .map(|dir| *dir != direction)
+                    .map(|(dir, _)| *dir != direction)
                     .unwrap_or(true)
                 {
-                    input_stack.push_back(direction);
+                    input_stack.push_back((direction, read_at));
                 }
             }
-            GameState::TitleScreen | GameState::GameOver => {}
+            GameState::TitleScreen
+            | GameState::Paused
+            | GameState::InGameMenu
+            | GameState::Settings
+            | GameState::Controls
+            | GameState::HighScores
+            | GameState::Stats
+            | GameState::GameOver
+            | GameState::ResumeOffer
+            | GameState::Exited => {}
         }
     }
 
     fn start_game(&mut self) {
         match self.model.game_state() {
             GameState::TitleScreen | GameState::GameOver => self.model.start_game(),
-            GameState::Playing => {}
+            GameState::Playing
+            | GameState::Paused
+            | GameState::InGameMenu
+            | GameState::Settings
+            | GameState::Controls
+            | GameState::HighScores
+            | GameState::Stats
+            | GameState::ResumeOffer
+            | GameState::Exited => {}
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,42 @@
+use std::{env, fs, path::PathBuf, process, sync::mpsc, time::Instant};
+
+use super::{Action, Controller};
+use crate::{
+    event_processes::TickInterval,
+    model::{title_menu::MenuInput, GameState, Model, Snapshot},
+    storage::Dirs,
+    views::{recorder::Recorder, tui_view::TuiView},
+    Config,
+};
+
+//a game in the title screen, drawn into a buffer, that keeps its files
+//in a directory of its own instead of the ones of the player
+fn controller(name: &str) -> (Controller, PathBuf) {
+    let root = env::temp_dir().join(format!("rusty-rattle-{name}-{}", process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let config = Config::builder().set_dirs(Dirs::under(&root)).build();
+    let view = Box::new(TuiView::with_writer(Vec::new()));
+    let model = Model::new(view, config, Recorder::new());
+    let tick_interval = TickInterval::new(model.settings().update_interval);
+    (Controller::new(model, tick_interval), root)
+}
+
+#[test]
+fn test_snapshots_before_the_first_game() {
+    let (mut controller, root) = controller("snapshots");
+    let (sender, receiver) = mpsc::channel();
+    controller.subscribe(sender);
+
+    //the controls opened from the title screen, with no snake on the board yet
+    for _ in 0..5 {
+        controller.handle_action(Action::Menu(MenuInput::Down), Instant::now());
+    }
+    controller.handle_action(Action::Menu(MenuInput::Select), Instant::now());
+    controller.update_model();
+
+    let snapshot: Snapshot = serde_json::from_str(&receiver.recv().unwrap()).unwrap();
+    assert_eq!(snapshot.state, GameState::Controls);
+    assert!(snapshot.snake.is_empty());
+    assert_eq!(snapshot.apple, None);
+    let _ = fs::remove_dir_all(root);
+}
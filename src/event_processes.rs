@@ -1,7 +1,11 @@
 use std::{
-    sync::mpsc::{Receiver, Sender},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{Receiver, Sender},
+        Arc,
+    },
     thread,
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use crossterm::event::Event as CTEvent;
@@ -10,12 +14,22 @@ use crossterm::{
     terminal,
 };
 
-use crate::{controller::Controller, Config};
+use crate::{
+    controller::{Action, Controller},
+    model::animation::FRAME_INTERVAL,
+};
 
 pub enum Event {
-    Input(KeyCode),
+    //the instant is when the key was read by the input loop
+    Input(KeyCode, Instant),
+    //an action coming from a remote client, with the instant it was received
+    Action(Action, Instant),
+    //a remote client asking for a snapshot of the game after each tick
+    Subscribe(Sender<String>),
     Quit,
     Tick,
+    //a frame of the animations, on a clock of its own
+    Frame,
 }
 
 pub fn event_loop(receiver: Receiver<Event>, mut controller: Controller) {
@@ -23,38 +37,32 @@ pub fn event_loop(receiver: Receiver<Event>, mut controller: Controller) {
         match event {
             Event::Quit => break,
             Event::Tick => controller.update_model(),
-            Event::Input(key_code) => controller.handle_input(key_code),
+            Event::Frame => controller.animate(),
+            Event::Input(key_code, read_at) => controller.handle_input(key_code, read_at),
+            Event::Action(action, read_at) => controller.handle_action(action, read_at),
+            Event::Subscribe(subscriber) => controller.subscribe(subscriber),
+        }
+        if controller.has_exited() {
+            break;
         }
     }
+    controller.quit();
 }
 
 pub fn input_loop(sender: Sender<Event>) {
     terminal::enable_raw_mode().unwrap();
     loop {
         if event::poll(std::time::Duration::from_millis(500)).unwrap() {
-            let result = match event::read().unwrap() {
-                // Exit on Esc key press
-                CTEvent::Key(event::KeyEvent {
-                    modifiers: _,
-                    state: _,
-                    kind: KeyEventKind::Press,
-                    code: KeyCode::Esc,
-                }) => {
-                    sender.send(Event::Quit).unwrap();
-                    break;
-                }
+            let event = event::read().unwrap();
+            let read_at = Instant::now();
+            let result = match event {
+                //the controller decides what each key does, esc included
                 CTEvent::Key(event::KeyEvent {
                     modifiers: _,
                     state: _,
                     kind: KeyEventKind::Press,
-                    code: KeyCode::Char(c @ ('w' | 'a' | 's' | 'd')),
-                }) => sender.send(Event::Input(KeyCode::Char(c))),
-                CTEvent::Key(event::KeyEvent {
-                    modifiers: _,
-                    state: _,
-                    kind: KeyEventKind::Press,
-                    code: KeyCode::Enter,
-                }) => sender.send(Event::Input(KeyCode::Enter)),
+                    code: key_code,
+                }) => sender.send(Event::Input(key_code, read_at)),
                 _ => Ok(()),
             };
             match result {
@@ -66,7 +74,26 @@ pub fn input_loop(sender: Sender<Event>) {
     terminal::disable_raw_mode().unwrap();
 }
 
-pub fn game_tick_loop(sender: Sender<Event>, config: Config) {
+//the time between two ticks, shared with the controller so that
+//every game can be played at the speed chosen in the title screen
+#[derive(Clone, Debug)]
+pub struct TickInterval(Arc<AtomicU64>);
+
+impl TickInterval {
+    pub fn new(interval: Duration) -> Self {
+        TickInterval(Arc::new(AtomicU64::new(interval.as_micros() as u64)))
+    }
+
+    pub fn get(&self) -> Duration {
+        Duration::from_micros(self.0.load(Ordering::Relaxed))
+    }
+
+    pub fn set(&self, interval: Duration) {
+        self.0.store(interval.as_micros() as u64, Ordering::Relaxed);
+    }
+}
+
+pub fn game_tick_loop(sender: Sender<Event>, tick_interval: TickInterval) {
     loop {
         let start = Instant::now();
 
@@ -75,9 +102,20 @@ pub fn game_tick_loop(sender: Sender<Event>, config: Config) {
         }
 
         //calculate elapsed time
+        let update_interval = tick_interval.get();
         let time_elapsed = start.elapsed();
-        if time_elapsed < config.update_interval {
-            thread::sleep(config.update_interval - time_elapsed);
+        if time_elapsed < update_interval {
+            thread::sleep(update_interval - time_elapsed);
+        }
+    }
+}
+
+//the animations move on at the same pace whatever the speed of the game
+pub fn render_loop(sender: Sender<Event>) {
+    loop {
+        thread::sleep(FRAME_INTERVAL);
+        if sender.send(Event::Frame).is_err() {
+            break;
         }
     }
 }
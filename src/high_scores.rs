@@ -0,0 +1,167 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    locale::{self, Messages},
+    settings::{GameMode, Settings},
+    storage::{self, Dirs},
+};
+
+//how many scores are kept for every board, speed and mode
+pub const TABLE_LEN: usize = 10;
+pub const INITIALS_LEN: usize = 3;
+
+const HIGH_SCORES_FILE: &str = "high_scores.json";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub initials: String,
+    pub score: usize,
+}
+
+//the settings that change how hard a game is, scores are only
+//compared with the ones played the same way
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScoreKey {
+    pub grid_height: usize,
+    pub grid_width: usize,
+    pub update_interval_ms: u64,
+    pub mode: GameMode,
+}
+
+impl ScoreKey {
+    pub fn new(settings: &Settings) -> Self {
+        ScoreKey {
+            grid_height: settings.grid_height,
+            grid_width: settings.grid_width,
+            update_interval_ms: settings.update_interval.as_millis() as u64,
+            mode: settings.mode,
+        }
+    }
+
+    pub fn describe(&self, messages: &Messages) -> String {
+        locale::fill(
+            messages.score_settings,
+            &[
+                ("height", &self.grid_height),
+                ("width", &self.grid_width),
+                ("interval", &self.update_interval_ms),
+                ("mode", &messages.game_mode(self.mode)),
+            ],
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Table {
+    settings: ScoreKey,
+    //from the best score down
+    scores: Vec<HighScore>,
+}
+
+//the best scores of every way the game was played, kept between runs
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HighScores {
+    tables: Vec<Table>,
+}
+
+impl HighScores {
+    //the scores saved in the data directory, none if there are none yet,
+    //an error when the file is there but can't be read, so it isn't saved over
+    pub fn load(dirs: &Dirs) -> io::Result<HighScores> {
+        let Some(path) = Self::high_scores_path(dirs) else {
+            return Ok(HighScores::default());
+        };
+        Self::move_from_state_dir(dirs, &path)?;
+        Self::load_or_set_aside(&path)
+    }
+
+    //the scores were kept in the state directory before, the file found there
+    //is moved next to the stats the first time, unless there are scores already
+    fn move_from_state_dir(dirs: &Dirs, path: &Path) -> io::Result<()> {
+        let Some(old_path) = dirs.state.as_ref().map(|dir| dir.join(HIGH_SCORES_FILE)) else {
+            return Ok(());
+        };
+        if path.exists() || !old_path.exists() {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        //the two directories can be on different file systems
+        fs::rename(&old_path, path).or_else(|_| {
+            fs::copy(&old_path, path)?;
+            fs::remove_file(&old_path)
+        })
+    }
+
+    //a file that can't be parsed is moved aside and the table starts over
+    pub fn load_or_set_aside(path: &Path) -> io::Result<HighScores> {
+        storage::load_or_set_aside(path, Self::load_file).map(Option::unwrap_or_default)
+    }
+
+    pub fn load_file(path: &Path) -> io::Result<HighScores> {
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, dirs: &Dirs) -> io::Result<()> {
+        let path = Self::high_scores_path(dirs)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        self.save_file(&path)
+    }
+
+    //written whole to a new file first, so a crash can't leave half of it behind
+    pub fn save_file(&self, path: &Path) -> io::Result<()> {
+        let contents = serde_json::to_vec_pretty(self).map_err(io::Error::other)?;
+        storage::write_atomic(path, &contents)
+    }
+
+    pub fn scores(&self, key: &ScoreKey) -> &[HighScore] {
+        self.tables
+            .iter()
+            .find(|table| table.settings == *key)
+            .map_or(&[], |table| &table.scores)
+    }
+
+    //the place a score would take in the table, None if it is not good enough
+    pub fn rank(&self, key: &ScoreKey, score: usize) -> Option<usize> {
+        let scores = self.scores(key);
+        let rank = scores
+            .iter()
+            .take_while(|other| other.score >= score)
+            .count();
+        (score > 0 && rank < TABLE_LEN).then_some(rank)
+    }
+
+    //ties go after the scores that were there first,
+    //returns the place the score took if it made the table
+    pub fn insert(&mut self, key: &ScoreKey, high_score: HighScore) -> Option<usize> {
+        let rank = self.rank(key, high_score.score)?;
+        let index = match self.tables.iter().position(|table| table.settings == *key) {
+            Some(index) => index,
+            None => {
+                self.tables.push(Table {
+                    settings: *key,
+                    scores: Vec::new(),
+                });
+                self.tables.len() - 1
+            }
+        };
+        let scores = &mut self.tables[index].scores;
+        scores.insert(rank, high_score);
+        scores.truncate(TABLE_LEN);
+        Some(rank)
+    }
+
+    fn high_scores_path(dirs: &Dirs) -> Option<PathBuf> {
+        Some(dirs.data.as_ref()?.join(HIGH_SCORES_FILE))
+    }
+}
+
+#[cfg(test)]
+mod tests;
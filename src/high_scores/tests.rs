@@ -0,0 +1,147 @@
+use std::{env, fs, process, time::Duration};
+
+use super::{HighScore, HighScores, ScoreKey, TABLE_LEN};
+use crate::{
+    locale::Locale,
+    settings::{GameMode, Settings},
+    storage::Dirs,
+};
+
+fn key(grid_width: usize) -> ScoreKey {
+    ScoreKey::new(&Settings {
+        grid_width,
+        ..Settings::default()
+    })
+}
+
+fn high_score(initials: &str, score: usize) -> HighScore {
+    HighScore {
+        initials: initials.to_string(),
+        score,
+    }
+}
+
+#[test]
+fn test_scores_are_kept_in_order() {
+    let mut high_scores = HighScores::default();
+    assert_eq!(high_scores.insert(&key(30), high_score("AAA", 20)), Some(0));
+    assert_eq!(high_scores.insert(&key(30), high_score("BBB", 40)), Some(0));
+    //ties go after the score that was there first
+    assert_eq!(high_scores.insert(&key(30), high_score("CCC", 20)), Some(2));
+    let initials: Vec<_> = high_scores
+        .scores(&key(30))
+        .iter()
+        .map(|high_score| high_score.initials.as_str())
+        .collect();
+    assert_eq!(initials, ["BBB", "AAA", "CCC"]);
+}
+
+#[test]
+fn test_only_the_best_scores_are_kept() {
+    let mut high_scores = HighScores::default();
+    for score in 1..=TABLE_LEN {
+        high_scores.insert(&key(30), high_score("AAA", score * 10));
+    }
+    assert_eq!(high_scores.rank(&key(30), 10), None);
+    assert_eq!(high_scores.rank(&key(30), 15), Some(TABLE_LEN - 1));
+    assert_eq!(high_scores.insert(&key(30), high_score("BBB", 5)), None);
+    assert_eq!(
+        high_scores.insert(&key(30), high_score("BBB", 200)),
+        Some(0)
+    );
+
+    let scores = high_scores.scores(&key(30));
+    assert_eq!(scores.len(), TABLE_LEN);
+    assert_eq!(scores[0].score, 200);
+    assert_eq!(scores[TABLE_LEN - 1].score, 20);
+    //a game without apples is not a high score
+    assert_eq!(HighScores::default().rank(&key(30), 0), None);
+}
+
+#[test]
+fn test_settings_have_their_own_tables() {
+    let mut high_scores = HighScores::default();
+    high_scores.insert(&key(30), high_score("AAA", 100));
+    assert_eq!(high_scores.rank(&key(40), 10), Some(0));
+    assert!(high_scores.scores(&key(40)).is_empty());
+
+    //the theme doesn't change how hard the game is
+    let settings = Settings::default();
+    let themed = Settings {
+        theme: crate::views::Theme::available()[1].clone(),
+        ..settings.clone()
+    };
+    assert_eq!(ScoreKey::new(&settings), ScoreKey::new(&themed));
+    let faster = Settings {
+        update_interval: Duration::from_millis(100),
+        ..settings.clone()
+    };
+    assert_ne!(ScoreKey::new(&settings), ScoreKey::new(&faster));
+    let wrap = Settings {
+        mode: GameMode::Wrap,
+        ..settings
+    };
+    assert_eq!(
+        ScoreKey::new(&wrap).describe(Locale::English.messages()),
+        "30x20, 200ms, wrap"
+    );
+}
+
+#[test]
+fn test_save_and_load() {
+    let path = env::temp_dir().join(format!("rusty-rattle-scores-{}.json", process::id()));
+    let mut high_scores = HighScores::default();
+    high_scores.insert(&key(30), high_score("AAA", 100));
+    high_scores.insert(&key(40), high_score("BBB", 50));
+    high_scores.save_file(&path).unwrap();
+    assert_eq!(HighScores::load_file(&path).unwrap(), high_scores);
+
+    fs::write(&path, "{\"tables\": [").unwrap();
+    assert!(HighScores::load_file(&path).is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_unreadable_scores_are_set_aside() {
+    let path = env::temp_dir().join(format!("rusty-rattle-bad-scores-{}.json", process::id()));
+    let bad_path = path.with_extension("json.bad");
+    //no scores yet
+    assert_eq!(
+        HighScores::load_or_set_aside(&path).unwrap(),
+        HighScores::default()
+    );
+
+    //the table starts over, and the file is kept for the player to fix
+    fs::write(&path, "{\"tables\": [").unwrap();
+    assert_eq!(
+        HighScores::load_or_set_aside(&path).unwrap(),
+        HighScores::default()
+    );
+    assert!(!path.exists());
+    assert_eq!(fs::read_to_string(&bad_path).unwrap(), "{\"tables\": [");
+    fs::remove_file(&bad_path).unwrap();
+}
+
+#[test]
+fn test_scores_move_to_the_data_dir() {
+    let root = env::temp_dir().join(format!("rusty-rattle-moved-scores-{}", process::id()));
+    let dirs = Dirs::under(&root);
+    let mut high_scores = HighScores::default();
+    high_scores.insert(&key(30), high_score("AAA", 50));
+    let old_path = dirs.state.as_ref().unwrap().join("high_scores.json");
+    high_scores.save_file(&old_path).unwrap();
+
+    assert_eq!(HighScores::load(&dirs).unwrap(), high_scores);
+    assert!(!old_path.exists());
+    assert!(dirs
+        .data
+        .as_ref()
+        .unwrap()
+        .join("high_scores.json")
+        .exists());
+
+    //an old file left behind never replaces the scores already moved
+    HighScores::default().save_file(&old_path).unwrap();
+    assert_eq!(HighScores::load(&dirs).unwrap(), high_scores);
+    fs::remove_dir_all(root).unwrap();
+}
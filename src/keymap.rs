@@ -0,0 +1,278 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crossterm::event::KeyCode;
+use toml::Value;
+
+use crate::{
+    locale::Messages,
+    storage::{self, Dirs},
+};
+
+const KEYMAP_FILE: &str = "keymap.toml";
+
+//everything the player can do with a single key
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KeyAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Pause,
+    Confirm,
+    Menu,
+    LatencyOverlay,
+    Record,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 9] = [
+        KeyAction::Up,
+        KeyAction::Down,
+        KeyAction::Left,
+        KeyAction::Right,
+        KeyAction::Pause,
+        KeyAction::Confirm,
+        KeyAction::Menu,
+        KeyAction::LatencyOverlay,
+        KeyAction::Record,
+    ];
+
+    //the name used in the keymap file
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyAction::Up => "up",
+            KeyAction::Down => "down",
+            KeyAction::Left => "left",
+            KeyAction::Right => "right",
+            KeyAction::Pause => "pause",
+            KeyAction::Confirm => "confirm",
+            KeyAction::Menu => "menu",
+            KeyAction::LatencyOverlay => "latency_overlay",
+            KeyAction::Record => "record",
+        }
+    }
+
+    fn default_key(&self) -> KeyCode {
+        match self {
+            KeyAction::Up => KeyCode::Char('w'),
+            KeyAction::Down => KeyCode::Char('s'),
+            KeyAction::Left => KeyCode::Char('a'),
+            KeyAction::Right => KeyCode::Char('d'),
+            KeyAction::Pause => KeyCode::Char('p'),
+            KeyAction::Confirm => KeyCode::Enter,
+            KeyAction::Menu => KeyCode::Esc,
+            KeyAction::LatencyOverlay => KeyCode::F(3),
+            KeyAction::Record => KeyCode::F(5),
+        }
+    }
+
+    fn from_name(name: &str) -> Option<KeyAction> {
+        KeyAction::ALL
+            .into_iter()
+            .find(|action| action.name() == name)
+    }
+}
+
+//a key bound to another action, so it cannot be bound before that one is changed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyConflict {
+    pub key: KeyCode,
+    pub bound_to: KeyAction,
+}
+
+//which key triggers each action, every action has exactly one key
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keymap {
+    bindings: BTreeMap<KeyAction, KeyCode>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap {
+            bindings: KeyAction::ALL
+                .into_iter()
+                .map(|action| (action, action.default_key()))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    pub fn action_for(&self, key: KeyCode) -> Option<KeyAction> {
+        self.bindings
+            .iter()
+            .find(|(_, bound_key)| **bound_key == key)
+            .map(|(action, _)| *action)
+    }
+
+    pub fn key_for(&self, action: KeyAction) -> KeyCode {
+        self.bindings[&action]
+    }
+
+    pub fn bind(&mut self, action: KeyAction, key: KeyCode) -> Result<(), KeyConflict> {
+        match self.action_for(key) {
+            Some(bound_to) if bound_to != action => Err(KeyConflict { key, bound_to }),
+            _ => {
+                self.bindings.insert(action, key);
+                Ok(())
+            }
+        }
+    }
+
+    //every action with the name of its key, for the help text
+    pub fn help(&self, messages: &Messages) -> Vec<(String, &'static str)> {
+        self.bindings
+            .iter()
+            .map(|(action, key)| (key_name(*key), messages.action(*action)))
+            .collect()
+    }
+
+    //the keymap saved in the user's config, or the default one
+    pub fn load(dirs: &Dirs) -> io::Result<Keymap> {
+        match Self::keymap_path(dirs) {
+            Some(path) => Self::load_or_set_aside(&path),
+            None => Ok(Keymap::default()),
+        }
+    }
+
+    //a file that can't be parsed is moved aside and the default keys are used,
+    //so binding a key doesn't save them over the ones it held
+    pub fn load_or_set_aside(path: &Path) -> io::Result<Keymap> {
+        storage::load_or_set_aside(path, Self::load_file).map(Option::unwrap_or_default)
+    }
+
+    //the default keymap when the file does not exist yet,
+    //it is created the first time a key is bound
+    pub fn load_file(path: &Path) -> io::Result<Keymap> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                Self::parse(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Keymap::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    //a broken file given to use is reported before the game starts,
+    //rather than silently replaced by the defaults
+    pub fn check_file(path: &Path) -> Result<(), String> {
+        Self::load_file(path)
+            .map(|_| ())
+            .map_err(|e| format!("invalid keymap {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, dirs: &Dirs) -> io::Result<()> {
+        let path = Self::keymap_path(dirs)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        self.save_file(&path)
+    }
+
+    pub fn save_file(&self, path: &Path) -> io::Result<()> {
+        storage::write_atomic(path, self.to_toml().as_bytes())
+    }
+
+    //actions missing from the file keep their default key, unknown actions,
+    //keys that can't be read and keys bound twice are errors, like in the controls screen
+    pub fn parse(contents: &str) -> Result<Keymap, String> {
+        let table: BTreeMap<String, String> =
+            toml::from_str(contents).map_err(|e| e.message().to_string())?;
+        let mut bindings = Vec::new();
+        for (name, key) in &table {
+            let action =
+                KeyAction::from_name(name).ok_or_else(|| format!("unknown action {name:?}"))?;
+            let key = parse_key(key).ok_or_else(|| format!("unknown key {key:?} for {name}"))?;
+            bindings.push((action, key));
+        }
+
+        //the actions in the file give up their default keys first,
+        //so they can be swapped between each other
+        let mut keymap = Keymap::default();
+        for (action, _) in &bindings {
+            keymap.bindings.remove(action);
+        }
+        for (action, key) in bindings {
+            keymap.bind(action, key).map_err(|conflict| {
+                format!(
+                    "{} is bound to both {} and {}",
+                    key_name(conflict.key),
+                    conflict.bound_to.name(),
+                    action.name()
+                )
+            })?;
+        }
+        Ok(keymap)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::from("# rusty-rattle key bindings\n");
+        for (action, key) in &self.bindings {
+            //keys like " and \ need escaping to stay a string
+            let key = Value::String(key_name(*key));
+            writeln!(toml, "{} = {key}", action.name()).unwrap();
+        }
+        toml
+    }
+
+    fn keymap_path(dirs: &Dirs) -> Option<PathBuf> {
+        Some(dirs.config.as_ref()?.join(KEYMAP_FILE))
+    }
+}
+
+pub fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("f{n}"),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::Home => "home".to_string(),
+        KeyCode::End => "end".to_string(),
+        KeyCode::PageUp => "pageup".to_string(),
+        KeyCode::PageDown => "pagedown".to_string(),
+        KeyCode::Insert => "insert".to_string(),
+        KeyCode::Delete => "delete".to_string(),
+        key => format!("{key:?}").to_lowercase(),
+    }
+}
+
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    match name.to_lowercase().as_str() {
+        "space" => Some(KeyCode::Char(' ')),
+        "up" => Some(KeyCode::Up),
+        "down" => Some(KeyCode::Down),
+        "left" => Some(KeyCode::Left),
+        "right" => Some(KeyCode::Right),
+        "enter" => Some(KeyCode::Enter),
+        "esc" => Some(KeyCode::Esc),
+        "backspace" => Some(KeyCode::Backspace),
+        "tab" => Some(KeyCode::Tab),
+        "home" => Some(KeyCode::Home),
+        "end" => Some(KeyCode::End),
+        "pageup" => Some(KeyCode::PageUp),
+        "pagedown" => Some(KeyCode::PageDown),
+        "insert" => Some(KeyCode::Insert),
+        "delete" => Some(KeyCode::Delete),
+        name => name
+            .strip_prefix('f')
+            .and_then(|n| n.parse().ok())
+            .map(KeyCode::F),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,110 @@
+use std::{env, fs, process};
+
+use crossterm::event::KeyCode;
+
+use super::{key_name, parse_key, KeyAction, KeyConflict, Keymap};
+
+#[test]
+fn test_bind_detects_conflicts() {
+    let mut keymap = Keymap::default();
+    assert_eq!(keymap.action_for(KeyCode::Char('w')), Some(KeyAction::Up));
+
+    assert_eq!(
+        keymap.bind(KeyAction::Up, KeyCode::Char('p')),
+        Err(KeyConflict {
+            key: KeyCode::Char('p'),
+            bound_to: KeyAction::Pause
+        })
+    );
+    assert_eq!(keymap.key_for(KeyAction::Up), KeyCode::Char('w'));
+
+    //binding an action to its own key is not a conflict
+    assert_eq!(keymap.bind(KeyAction::Up, KeyCode::Char('w')), Ok(()));
+
+    assert_eq!(keymap.bind(KeyAction::Up, KeyCode::Up), Ok(()));
+    assert_eq!(keymap.action_for(KeyCode::Up), Some(KeyAction::Up));
+    assert_eq!(keymap.action_for(KeyCode::Char('w')), None);
+}
+
+#[test]
+fn test_keymap_toml_round_trip() {
+    let mut keymap = Keymap::default();
+    keymap.bind(KeyAction::Pause, KeyCode::Char(' ')).unwrap();
+    keymap
+        .bind(KeyAction::LatencyOverlay, KeyCode::F(12))
+        .unwrap();
+    keymap.bind(KeyAction::Up, KeyCode::Char('"')).unwrap();
+    keymap.bind(KeyAction::Down, KeyCode::Char('\\')).unwrap();
+
+    assert_eq!(Keymap::parse(&keymap.to_toml()), Ok(keymap));
+}
+
+#[test]
+fn test_parse_partial_keymap() {
+    let keymap = Keymap::parse("up = \"i\"\n").unwrap();
+    assert_eq!(keymap.key_for(KeyAction::Up), KeyCode::Char('i'));
+    assert_eq!(keymap.key_for(KeyAction::Left), KeyCode::Char('a'));
+    assert_eq!(keymap.key_for(KeyAction::Down), KeyCode::Char('s'));
+
+    assert!(Keymap::parse("up = ").is_err());
+}
+
+#[test]
+fn test_parse_rejects_broken_bindings() {
+    assert_eq!(
+        Keymap::parse("jump = \"j\"\n"),
+        Err("unknown action \"jump\"".to_string())
+    );
+    assert_eq!(
+        Keymap::parse("left = \"not a key\"\n"),
+        Err("unknown key \"not a key\" for left".to_string())
+    );
+    //with each other, or with the default key of an action not in the file
+    assert_eq!(
+        Keymap::parse("up = \"i\"\ndown = \"i\"\n"),
+        Err("i is bound to both down and up".to_string())
+    );
+    assert_eq!(
+        Keymap::parse("up = \"p\"\n"),
+        Err("p is bound to both pause and up".to_string())
+    );
+
+    //keys can be swapped between the actions in the file
+    let keymap = Keymap::parse("up = \"s\"\ndown = \"w\"\n").unwrap();
+    assert_eq!(keymap.action_for(KeyCode::Char('s')), Some(KeyAction::Up));
+    assert_eq!(keymap.action_for(KeyCode::Char('w')), Some(KeyAction::Down));
+}
+
+#[test]
+fn test_key_names() {
+    for key in [
+        KeyCode::Char('w'),
+        KeyCode::Char(' '),
+        KeyCode::Char('F'),
+        KeyCode::F(3),
+        KeyCode::Esc,
+        KeyCode::Enter,
+        KeyCode::Left,
+    ] {
+        assert_eq!(parse_key(&key_name(key)), Some(key));
+    }
+    assert_eq!(parse_key("Esc"), Some(KeyCode::Esc));
+    assert_eq!(parse_key("nope"), None);
+}
+
+#[test]
+fn test_unreadable_keymaps_are_set_aside() {
+    let path = env::temp_dir().join(format!("rusty-rattle-bad-keymap-{}.toml", process::id()));
+    let bad_path = path.with_extension("toml.bad");
+    fs::write(&path, "up = \"w\"\ndown = \"w\"\n").unwrap();
+    let keymap = Keymap::load_or_set_aside(&path).unwrap();
+    assert_eq!(keymap.key_for(KeyAction::Up), KeyCode::Char('w'));
+    assert_eq!(keymap.key_for(KeyAction::Down), KeyCode::Char('s'));
+
+    //binding a key saves a new file next to the old bindings instead of over them
+    assert!(!path.exists());
+    assert!(fs::read_to_string(&bad_path)
+        .unwrap()
+        .contains("down = \"w\""));
+    fs::remove_file(&bad_path).unwrap();
+}
@@ -0,0 +1,161 @@
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+//bucket i counts the samples in [2^i, 2^(i+1)) microseconds
+const BUCKETS: usize = 32;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    buckets: [u64; BUCKETS],
+    count: u64,
+    sum: Duration,
+    min: Option<Duration>,
+    max: Option<Duration>,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram {
+            buckets: [0; BUCKETS],
+            count: 0,
+            sum: Duration::ZERO,
+            min: None,
+            max: None,
+        }
+    }
+}
+
+impl Histogram {
+    pub fn record(&mut self, sample: Duration) {
+        self.buckets[Self::bucket_index(sample)] += 1;
+        self.count += 1;
+        self.sum += sample;
+        self.min = Some(self.min.map_or(sample, |min| min.min(sample)));
+        self.max = Some(self.max.map_or(sample, |max| max.max(sample)));
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.sum / self.count as u32)
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.max
+    }
+
+    //returns the upper bound of the bucket containing the requested percentile,
+    //clamped to the largest recorded sample
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        let rank = ((percentile / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            seen += bucket;
+            if seen >= rank {
+                let upper_bound = Duration::from_micros(1 << (i + 1));
+                return Some(upper_bound.min(self.max.unwrap_or(upper_bound)));
+            }
+        }
+        self.max
+    }
+
+    fn bucket_index(sample: Duration) -> usize {
+        let micros = sample.as_micros().max(1);
+        let index = (u128::BITS - 1 - micros.leading_zeros()) as usize;
+        index.min(BUCKETS - 1)
+    }
+
+    fn summary(&self, name: &str) -> String {
+        match (self.percentile(50.0), self.percentile(99.0), self.max) {
+            (Some(p50), Some(p99), Some(max)) => format!(
+                "{name}: n={} p50={} p99={} max={}",
+                self.count,
+                format_ms(p50),
+                format_ms(p99),
+                format_ms(max)
+            ),
+            _ => format!("{name}: no samples"),
+        }
+    }
+
+    fn dump(&self, name: &str, out: &mut String) {
+        writeln!(out, "[{name}]").unwrap();
+        writeln!(out, "count = {}", self.count).unwrap();
+        if let (Some(mean), Some(min), Some(max)) = (self.mean(), self.min, self.max) {
+            writeln!(out, "mean_us = {}", mean.as_micros()).unwrap();
+            writeln!(out, "min_us = {}", min.as_micros()).unwrap();
+            writeln!(out, "max_us = {}", max.as_micros()).unwrap();
+        }
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            if *bucket > 0 {
+                writeln!(
+                    out,
+                    "bucket_{}us_{}us = {bucket}",
+                    1u64 << i,
+                    1u64 << (i + 1)
+                )
+                .unwrap();
+            }
+        }
+    }
+}
+
+//measures how long it takes for an input to be applied by a tick
+//and to be shown by the view
+#[derive(Clone, Debug, Default)]
+pub struct LatencyTracker {
+    input_to_apply: Histogram,
+    input_to_pixel: Histogram,
+}
+
+impl LatencyTracker {
+    pub fn record_apply(&mut self, read_at: Instant) {
+        self.input_to_apply.record(read_at.elapsed());
+    }
+
+    pub fn record_pixel(&mut self, read_at: Instant) {
+        self.input_to_pixel.record(read_at.elapsed());
+    }
+
+    pub fn input_to_apply(&self) -> &Histogram {
+        &self.input_to_apply
+    }
+
+    pub fn input_to_pixel(&self) -> &Histogram {
+        &self.input_to_pixel
+    }
+
+    pub fn overlay_lines(&self) -> Vec<String> {
+        vec![
+            self.input_to_apply.summary("input->apply"),
+            self.input_to_pixel.summary("input->pixel"),
+        ]
+    }
+
+    pub fn dump_to_file(&self, path: &Path) -> io::Result<()> {
+        let mut out = String::new();
+        self.input_to_apply.dump("input_to_apply", &mut out);
+        out.push('\n');
+        self.input_to_pixel.dump("input_to_pixel", &mut out);
+        fs::write(path, out)
+    }
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use super::Histogram;
+
+#[test]
+fn test_histogram_percentiles() {
+    let mut histogram = Histogram::default();
+    assert_eq!(histogram.percentile(50.0), None);
+
+    for millis in 1..=100 {
+        histogram.record(Duration::from_millis(millis));
+    }
+
+    assert_eq!(histogram.count(), 100);
+    assert_eq!(histogram.max(), Some(Duration::from_millis(100)));
+    assert_eq!(histogram.mean(), Some(Duration::from_micros(50_500)));
+
+    //percentiles are reported as the upper bound of their bucket
+    let p50 = histogram.percentile(50.0).unwrap();
+    assert!(p50 >= Duration::from_millis(50) && p50 <= Duration::from_micros(65_536));
+    assert_eq!(
+        histogram.percentile(100.0),
+        Some(Duration::from_millis(100))
+    );
+}
+
+#[test]
+fn test_histogram_bucket_index() {
+    assert_eq!(Histogram::bucket_index(Duration::ZERO), 0);
+    assert_eq!(Histogram::bucket_index(Duration::from_micros(1)), 0);
+    assert_eq!(Histogram::bucket_index(Duration::from_micros(2)), 1);
+    assert_eq!(Histogram::bucket_index(Duration::from_micros(1023)), 9);
+    assert_eq!(Histogram::bucket_index(Duration::from_micros(1024)), 10);
+    assert_eq!(Histogram::bucket_index(Duration::from_secs(1_000_000)), 31);
+}
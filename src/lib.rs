@@ -1,72 +1,404 @@
-use std::time::Duration;
+use std::{net::SocketAddr, path::PathBuf, str::FromStr, time::Duration};
 
+use locale::Locale;
+use remote::RemoteAddr;
+use settings::{GameMode, Settings};
+use storage::Dirs;
+use views::{export::Exporter, glyphs::GlyphSet, tui_view::RenderMode, Theme};
+
+pub mod cli;
+pub mod config_file;
 pub mod controller;
 pub mod event_processes;
+pub mod high_scores;
+pub mod keymap;
+pub mod latency;
+pub mod locale;
 pub mod model;
+pub mod remote;
+pub mod settings;
+pub mod stats;
+pub mod storage;
 pub mod views;
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct Config {
     view_type: ViewType,
-    grid_height: usize,
-    grid_width: usize,
-    update_interval: Duration,
+    mirror_views: Vec<ViewType>,
+    //the settings given explicitly, over the ones of the last game
+    grid_height: Option<usize>,
+    grid_width: Option<usize>,
+    update_interval: Option<Duration>,
+    mode: Option<GameMode>,
+    theme: Option<Theme>,
+    seed: Option<u64>,
+    keymap: Option<PathBuf>,
+    play: bool,
+    glyphs: GlyphSet,
+    render_mode: RenderMode,
+    camera_dead_zone: (usize, usize),
+    minimap: bool,
+    latency_dump: Option<PathBuf>,
+    remote: Option<RemoteAddr>,
+    image_output: Option<PathBuf>,
+    web_addr: SocketAddr,
+    record: Option<PathBuf>,
+    export: Option<Exporter>,
+    locale: Locale,
+    dirs: Dirs,
 }
 
 impl Config {
+    //the settings offered in the title screen, the ones of the last game
+    //if there was one, with the ones set explicitly taking precedence
+    pub fn initial_settings(&self, last: Option<Settings>) -> Settings {
+        let mut settings = last.unwrap_or_default();
+        if let Some(grid_height) = self.grid_height {
+            settings.grid_height = grid_height;
+        }
+        if let Some(grid_width) = self.grid_width {
+            settings.grid_width = grid_width;
+        }
+        if let Some(update_interval) = self.update_interval {
+            settings.update_interval = update_interval;
+        }
+        if let Some(mode) = self.mode {
+            settings.mode = mode;
+        }
+        if let Some(theme) = &self.theme {
+            settings.theme = theme.clone();
+        }
+        settings
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn keymap(&self) -> Option<&PathBuf> {
+        self.keymap.as_ref()
+    }
+
+    pub fn play(&self) -> bool {
+        self.play
+    }
+
     pub fn view_type(&self) -> &ViewType {
         &self.view_type
     }
 
+    pub fn mirror_views(&self) -> &[ViewType] {
+        &self.mirror_views
+    }
+
+    pub fn glyphs(&self) -> GlyphSet {
+        self.glyphs
+    }
+
+    pub fn render_mode(&self) -> RenderMode {
+        self.render_mode
+    }
+
+    pub fn camera_dead_zone(&self) -> (usize, usize) {
+        self.camera_dead_zone
+    }
+
+    pub fn minimap(&self) -> bool {
+        self.minimap
+    }
+
+    pub fn latency_dump(&self) -> Option<&PathBuf> {
+        self.latency_dump.as_ref()
+    }
+
+    pub fn remote(&self) -> Option<&RemoteAddr> {
+        self.remote.as_ref()
+    }
+
+    pub fn image_output(&self) -> Option<&PathBuf> {
+        self.image_output.as_ref()
+    }
+
+    pub fn web_addr(&self) -> SocketAddr {
+        self.web_addr
+    }
+
+    pub fn record(&self) -> Option<&PathBuf> {
+        self.record.as_ref()
+    }
+
+    pub fn export(&self) -> Option<&Exporter> {
+        self.export.as_ref()
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    pub fn dirs(&self) -> &Dirs {
+        &self.dirs
+    }
+
     pub fn builder() -> ConfigBuilder {
         ConfigBuilder {
             view_type: None,
+            mirror_views: Vec::new(),
             grid_height: None,
             grid_width: None,
             update_interval: None,
+            mode: None,
+            theme: None,
+            seed: None,
+            keymap: None,
+            play: false,
+            glyphs: None,
+            render_mode: None,
+            camera_dead_zone: None,
+            minimap: None,
+            latency_dump: None,
+            remote: None,
+            image_output: None,
+            web_addr: None,
+            record: None,
+            export: None,
+            locale: None,
+            dirs: None,
         }
     }
 }
+
+#[derive(Clone)]
 pub struct ConfigBuilder {
     view_type: Option<ViewType>,
+    mirror_views: Vec<ViewType>,
     grid_height: Option<usize>,
     grid_width: Option<usize>,
     update_interval: Option<Duration>,
+    mode: Option<GameMode>,
+    theme: Option<Theme>,
+    seed: Option<u64>,
+    keymap: Option<PathBuf>,
+    play: bool,
+    glyphs: Option<GlyphSet>,
+    render_mode: Option<RenderMode>,
+    camera_dead_zone: Option<(usize, usize)>,
+    minimap: Option<bool>,
+    latency_dump: Option<PathBuf>,
+    remote: Option<RemoteAddr>,
+    image_output: Option<PathBuf>,
+    web_addr: Option<SocketAddr>,
+    record: Option<PathBuf>,
+    export: Option<Exporter>,
+    locale: Option<Locale>,
+    dirs: Option<Dirs>,
 }
 
 impl ConfigBuilder {
     pub fn set_view_type(mut self, view_type: ViewType) -> ConfigBuilder {
         self.view_type = Some(view_type);
-        return self;
+        self
+    }
+
+    //another view showing the same game, drawn on a thread of its own
+    pub fn add_mirror_view(mut self, view_type: ViewType) -> ConfigBuilder {
+        self.mirror_views.push(view_type);
+        self
+    }
+
+    pub fn clear_mirror_views(mut self) -> ConfigBuilder {
+        self.mirror_views.clear();
+        self
     }
 
     pub fn set_grid_height(mut self, grid_height: usize) -> ConfigBuilder {
         self.grid_height = Some(grid_height);
-        return self;
+        self
     }
 
     pub fn set_grid_width(mut self, grid_width: usize) -> ConfigBuilder {
         self.grid_width = Some(grid_width);
-        return self;
+        self
     }
 
     pub fn set_update_interval(mut self, update_interval: Duration) -> ConfigBuilder {
         self.update_interval = Some(update_interval);
-        return self;
+        self
+    }
+
+    pub fn set_mode(mut self, mode: GameMode) -> ConfigBuilder {
+        self.mode = Some(mode);
+        self
+    }
+
+    pub fn set_theme(mut self, theme: Theme) -> ConfigBuilder {
+        self.theme = Some(theme);
+        self
+    }
+
+    //the same seed places the apples in the same cells
+    pub fn set_seed(mut self, seed: u64) -> ConfigBuilder {
+        self.seed = Some(seed);
+        self
+    }
+
+    //a keymap file instead of the one in the user's config directory,
+    //rebinding keys saves to it
+    pub fn set_keymap(mut self, keymap: PathBuf) -> ConfigBuilder {
+        self.keymap = Some(keymap);
+        self
+    }
+
+    //starts a game right away instead of showing the title screen
+    pub fn set_play(mut self, play: bool) -> ConfigBuilder {
+        self.play = play;
+        self
+    }
+
+    //the characters the text view draws the board with
+    pub fn set_glyphs(mut self, glyphs: GlyphSet) -> ConfigBuilder {
+        self.glyphs = Some(glyphs);
+        self
+    }
+
+    //how many cells the text view packs in a character
+    pub fn set_render_mode(mut self, render_mode: RenderMode) -> ConfigBuilder {
+        self.render_mode = Some(render_mode);
+        self
+    }
+
+    //how many cells from the edges of the screen the head can get
+    //before a board bigger than the screen scrolls, across and down
+    pub fn set_camera_dead_zone(mut self, camera_dead_zone: (usize, usize)) -> ConfigBuilder {
+        self.camera_dead_zone = Some(camera_dead_zone);
+        self
+    }
+
+    pub fn set_minimap(mut self, minimap: bool) -> ConfigBuilder {
+        self.minimap = Some(minimap);
+        self
+    }
+
+    pub fn set_latency_dump(mut self, latency_dump: PathBuf) -> ConfigBuilder {
+        self.latency_dump = Some(latency_dump);
+        self
+    }
+
+    pub fn set_remote(mut self, remote: RemoteAddr) -> ConfigBuilder {
+        self.remote = Some(remote);
+        self
+    }
+
+    //a .png file overwritten with every frame, or a directory for a numbered sequence
+    pub fn set_image_output(mut self, image_output: PathBuf) -> ConfigBuilder {
+        self.image_output = Some(image_output);
+        self
+    }
+
+    //where the web view serves its page, localhost only by default
+    pub fn set_web_addr(mut self, web_addr: SocketAddr) -> ConfigBuilder {
+        self.web_addr = Some(web_addr);
+        self
+    }
+
+    //records the terminal view from the start, as an asciicast file
+    pub fn set_record(mut self, record: PathBuf) -> ConfigBuilder {
+        self.record = Some(record);
+        self
+    }
+
+    //renders every finished game with the exporter, next to the other views
+    pub fn set_export(mut self, export: Exporter) -> ConfigBuilder {
+        self.export = Some(export);
+        self
+    }
+
+    //the language of everything shown to the player
+    pub fn set_locale(mut self, locale: Locale) -> ConfigBuilder {
+        self.locale = Some(locale);
+        self
+    }
+
+    //the language set so far, English when there is none yet
+    pub fn locale(&self) -> Locale {
+        self.locale.unwrap_or_default()
+    }
+
+    //the language to use when none was set, the one of the system
+    pub fn fallback_locale(mut self, locale: Locale) -> ConfigBuilder {
+        self.locale.get_or_insert(locale);
+        self
+    }
+
+    //where the game reads and writes its files instead of the user's directories
+    pub fn set_dirs(mut self, dirs: Dirs) -> ConfigBuilder {
+        self.dirs = Some(dirs);
+        self
     }
 
     pub fn build(self) -> Config {
         Config {
             view_type: self.view_type.unwrap_or(ViewType::TUI),
-            grid_height: self.grid_height.unwrap_or(10),
-            grid_width: self.grid_width.unwrap_or(20),
-            update_interval: self.update_interval.unwrap_or(Duration::from_millis(200)),
+            mirror_views: self.mirror_views,
+            grid_height: self.grid_height,
+            grid_width: self.grid_width,
+            update_interval: self.update_interval,
+            mode: self.mode,
+            theme: self.theme,
+            seed: self.seed,
+            keymap: self.keymap,
+            play: self.play,
+            glyphs: self.glyphs.unwrap_or_default(),
+            render_mode: self.render_mode.unwrap_or_default(),
+            camera_dead_zone: self.camera_dead_zone.unwrap_or((5, 3)),
+            minimap: self.minimap.unwrap_or(false),
+            latency_dump: self.latency_dump,
+            remote: self.remote,
+            image_output: self.image_output,
+            web_addr: self
+                .web_addr
+                .unwrap_or(SocketAddr::from(([127, 0, 0, 1], 8080))),
+            record: self.record,
+            export: self.export,
+            locale: self.locale.unwrap_or_default(),
+            dirs: self.dirs.unwrap_or_else(Dirs::user),
         }
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ViewType {
+    //a headless view writing png images
     GUI,
     TUI,
+    //a page served to browsers on localhost
+    Web,
+    //plain text descriptions for screen readers
+    Narrated,
+}
+
+impl ViewType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ViewType::GUI => "gui",
+            ViewType::TUI => "tui",
+            ViewType::Web => "web",
+            ViewType::Narrated => "narrated",
+        }
+    }
+}
+
+impl FromStr for ViewType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "gui" => Ok(ViewType::GUI),
+            "tui" => Ok(ViewType::TUI),
+            "web" => Ok(ViewType::Web),
+            "narrated" => Ok(ViewType::Narrated),
+            _ => Err(format!(
+                "unknown view {s:?}, expected tui, gui, web or narrated"
+            )),
+        }
+    }
 }
@@ -0,0 +1,231 @@
+use std::{env, fmt::Display, str::FromStr};
+
+use serde::Serialize;
+
+use crate::{keymap::KeyAction, model::Orientation, settings::GameMode};
+
+mod en;
+mod it;
+
+//the language every text shown to the player is in
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Locale {
+    #[default]
+    English,
+    Italian,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::English, Locale::Italian];
+
+    //the language code, as in LANG
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Italian => "it",
+        }
+    }
+
+    pub const fn messages(&self) -> &'static Messages {
+        match self {
+            Locale::English => &en::MESSAGES,
+            Locale::Italian => &it::MESSAGES,
+        }
+    }
+
+    //the locale of the environment, like the C library picks it,
+    //English when it is unset or in a language without a bundle
+    pub fn from_env() -> Locale {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(var)
+            .find(|value| !value.is_empty())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+//a language code alone, or a full locale name like it_IT.UTF-8
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.code() == language)
+            .ok_or_else(|| format!("unknown locale {s:?}, expected en or it"))
+    }
+}
+
+//every text shown to the player, in a single language;
+//the ones with {names} in braces are filled in with fill
+#[derive(Debug, Serialize)]
+pub struct Messages {
+    //the title screen, the name of the game is drawn the same in every language
+    pub welcome: &'static str,
+    pub controls_heading: &'static str,
+    pub menu_hint: &'static str,
+    pub start_hint: &'static str,
+
+    //the settings in the title screen and in the in-game menu
+    pub grid_height: &'static str,
+    pub grid_width: &'static str,
+    pub step_interval: &'static str,
+    pub mode: &'static str,
+    pub theme: &'static str,
+    pub controls: &'static str,
+    pub mode_classic: &'static str,
+    pub mode_wrap: &'static str,
+    pub out_of_range: &'static str,
+
+    //the in-game menu
+    pub menu_title: &'static str,
+    pub settings_title: &'static str,
+    pub resume: &'static str,
+    pub restart: &'static str,
+    pub settings: &'static str,
+    pub quit_to_title: &'static str,
+    pub exit: &'static str,
+
+    //the controls screen
+    pub controls_hint: &'static str,
+    pub press_a_key: &'static str,
+    pub already_bound: &'static str,
+    pub action_up: &'static str,
+    pub action_down: &'static str,
+    pub action_left: &'static str,
+    pub action_right: &'static str,
+    pub action_pause: &'static str,
+    pub action_confirm: &'static str,
+    pub action_menu: &'static str,
+    pub action_latency_overlay: &'static str,
+    pub action_record: &'static str,
+
+    //the hud under the board and the panels drawn over it
+    pub hud: &'static str,
+    pub stats: &'static str,
+    pub paused: &'static str,
+    pub game_over: &'static str,
+    pub victory: &'static str,
+    pub your_score: &'static str,
+    pub hit_wall: &'static str,
+    pub hit_body: &'static str,
+    pub filled_board: &'static str,
+    pub continue_or_quit: &'static str,
+
+    //the high scores screen, and the initials asked for a new one
+    pub high_scores: &'static str,
+    pub no_scores: &'static str,
+    pub back_hint: &'static str,
+    pub score_settings: &'static str,
+    pub new_high_score: &'static str,
+    pub rank: &'static str,
+    pub your_initials: &'static str,
+    pub initials_hint: &'static str,
+
+    //the lifetime stats screen
+    pub lifetime_stats: &'static str,
+    pub games_played: &'static str,
+    pub apples_eaten: &'static str,
+    pub longest_snake: &'static str,
+    pub time_played: &'static str,
+    pub average_game: &'static str,
+    pub game_endings: &'static str,
+    pub left_unfinished: &'static str,
+
+    //the game saved when quitting, offered at the next launch
+    pub saved_game: &'static str,
+    pub resume_hint: &'static str,
+
+    //the web page, once the server is gone
+    pub disconnected: &'static str,
+
+    //what the narrated view reads out
+    pub narrated_title: &'static str,
+    pub narrated_controls: &'static str,
+    pub narrated_game_over: &'static str,
+    pub narrated_victory: &'static str,
+    pub narrated_end: &'static str,
+    pub narrated_back: &'static str,
+    pub heading: &'static str,
+    pub apple_at: &'static str,
+    pub cells_left: &'static str,
+    pub cells_right: &'static str,
+    pub cells_up: &'static str,
+    pub cells_down: &'static str,
+    pub obstacle_in: &'static str,
+    pub way_clear: &'static str,
+    pub apple_eaten: &'static str,
+    pub turned: &'static str,
+    pub danger: &'static str,
+    pub up: &'static str,
+    pub right: &'static str,
+    pub down: &'static str,
+    pub left: &'static str,
+    pub wall: &'static str,
+    pub body: &'static str,
+    pub tail: &'static str,
+
+    //the command line help and the errors in the arguments
+    pub usage: &'static str,
+    pub needs_value: &'static str,
+    pub speed_set_twice: &'static str,
+    pub invalid_seed: &'static str,
+    pub no_theme: &'static str,
+    pub unknown_argument: &'static str,
+    pub not_in_range: &'static str,
+}
+
+impl Messages {
+    pub fn action(&self, action: KeyAction) -> &'static str {
+        match action {
+            KeyAction::Up => self.action_up,
+            KeyAction::Down => self.action_down,
+            KeyAction::Left => self.action_left,
+            KeyAction::Right => self.action_right,
+            KeyAction::Pause => self.action_pause,
+            KeyAction::Confirm => self.action_confirm,
+            KeyAction::Menu => self.action_menu,
+            KeyAction::LatencyOverlay => self.action_latency_overlay,
+            KeyAction::Record => self.action_record,
+        }
+    }
+
+    pub fn game_mode(&self, mode: GameMode) -> &'static str {
+        match mode {
+            GameMode::Classic => self.mode_classic,
+            GameMode::Wrap => self.mode_wrap,
+        }
+    }
+
+    pub fn direction(&self, direction: Orientation) -> &'static str {
+        match direction {
+            Orientation::Up => self.up,
+            Orientation::Right => self.right,
+            Orientation::Down => self.down,
+            Orientation::Left => self.left,
+        }
+    }
+}
+
+//replaces every {name} in the template with the value given for it,
+//names without a value are left as they are
+pub fn fill(template: &str, values: &[(&str, &dyn Display)]) -> String {
+    let mut filled = template.to_string();
+    for (name, value) in values {
+        filled = filled.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests;
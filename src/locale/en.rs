@@ -0,0 +1,128 @@
+use super::Messages;
+
+pub(super) const MESSAGES: Messages = Messages {
+    welcome: "Welcome to:",
+    controls_heading: "controls:",
+    menu_hint: "arrows to choose, left/right or digits to change",
+    start_hint: "choose the settings and start the game",
+
+    grid_height: "grid height",
+    grid_width: "grid width",
+    step_interval: "step interval (ms)",
+    mode: "mode",
+    theme: "theme",
+    controls: "controls",
+    mode_classic: "classic",
+    mode_wrap: "wrap",
+    out_of_range: "must be between {min} and {max}",
+
+    menu_title: "menu",
+    settings_title: "settings",
+    resume: "resume",
+    restart: "restart",
+    settings: "settings",
+    quit_to_title: "quit to title",
+    exit: "exit",
+
+    controls_hint: "select an action to bind it to the next key pressed, go back with the menu key",
+    press_a_key: "press a key",
+    already_bound: "{key} is already bound to {action}",
+    action_up: "move up",
+    action_down: "move down",
+    action_left: "move left",
+    action_right: "move right",
+    action_pause: "pause",
+    action_confirm: "start / select",
+    action_menu: "menu / back / quit",
+    action_latency_overlay: "latency overlay",
+    action_record: "start / stop recording",
+
+    hud:
+        "score {score}  length {length}  apples {apples}  speed {speed}ms  time {time}  best {best}",
+    stats: "length {length}  apples {apples}  time {time}",
+    paused: "paused, press p to resume",
+    game_over: "GAME OVER",
+    victory: "YOU WIN",
+    your_score: "your score is: {score}",
+    hit_wall: "you hit the wall",
+    hit_body: "you ran into yourself",
+    filled_board: "the snake filled the whole board",
+    continue_or_quit: "press enter to continue, esc to quit",
+
+    high_scores: "high scores",
+    no_scores: "no scores yet",
+    back_hint: "go back with the menu key",
+    score_settings: "{width}x{height}, {interval}ms, {mode}",
+    new_high_score: "NEW HIGH SCORE",
+    rank: "number {rank} of the best scores",
+    your_initials: "your initials: {initials}",
+    initials_hint: "type them and press enter, esc to skip",
+
+    lifetime_stats: "stats",
+    games_played: "games played: {games}",
+    apples_eaten: "apples eaten: {apples}",
+    longest_snake: "longest snake: {length}",
+    time_played: "time played: {time}",
+    average_game: "average game: {time}",
+    game_endings: "how the games ended:",
+    left_unfinished: "you left before the end",
+
+    saved_game: "SAVED GAME",
+    resume_hint: "press enter to resume it, esc to start a new one",
+
+    disconnected: "the game is over, reload to play again",
+
+    narrated_title:
+        "rusty rattle. up and down to choose, left and right to change, enter to start. ",
+    narrated_controls: "controls. select an action to bind it to the next key pressed. ",
+    narrated_game_over: "game over",
+    narrated_victory: "you win, the snake fills the board",
+    narrated_end:
+        "{headline}, your score is {score}. length {length}, {apples} apples, {seconds} seconds. ",
+    narrated_back: "esc to go back.",
+    heading: "heading {direction} at column {column} row {row}. ",
+    apple_at: "apple {position}. ",
+    cells_left: "{cells} left",
+    cells_right: "{cells} right",
+    cells_up: "{cells} up",
+    cells_down: "{cells} down",
+    obstacle_in: "{direction} {obstacle} in {steps}",
+    way_clear: "{direction} clear",
+    apple_eaten: "apple eaten, length {length}. ",
+    turned: "turned {direction}. ",
+    danger: "danger, {obstacle} ahead in {steps}. ",
+    up: "up",
+    right: "right",
+    down: "down",
+    left: "left",
+    wall: "wall",
+    body: "body",
+    tail: "tail",
+
+    usage: "usage: rusty-rattle [options]
+
+  --height <cells>       the height of the board, 8 to 100
+  --width <cells>        the width of the board, 8 to 200
+  --interval-ms <ms>     how long a step takes, 20 to 2000
+  --level <n>            the speed as a level instead, from 1 (300ms) to 10 (30ms)
+  --seed <n>             places the apples the same way every time
+  --view <views>         tui, gui, web or narrated, more separated by commas
+  --mode <mode>          classic or wrap
+  --theme <name>         a preset theme or one from the themes directory
+  --keymap <file>        the key bindings to use and save to
+  --play                 starts a game right away instead of showing the title screen
+  --write-config         saves the options in effect to the config file and exits
+  -h, --help             shows this help
+  -V, --version          shows the version
+
+the board, speed, mode and theme are offered in the title screen,
+the ones given here replace the ones of the last game.
+the options are read from $XDG_CONFIG_HOME/rusty-rattle/config.toml first,
+then from the RUSTY_RATTLE_* environment variables and then from the flags",
+    needs_value: "{flag} needs a value",
+    speed_set_twice: "{other} and {flag} both set the speed, use one of them",
+    invalid_seed: "invalid seed {seed}: {error}",
+    no_theme: "no theme {name}, expected one of {names}",
+    unknown_argument: "unknown argument {argument}",
+    not_in_range: "{flag} must be a number between {min} and {max}, not {value}",
+};
@@ -0,0 +1,125 @@
+use super::Messages;
+
+pub(super) const MESSAGES: Messages = Messages {
+    welcome: "Benvenuto in:",
+    controls_heading: "comandi:",
+    menu_hint: "frecce per scegliere, sinistra/destra o cifre per cambiare",
+    start_hint: "scegli le impostazioni e inizia la partita",
+
+    grid_height: "altezza griglia",
+    grid_width: "larghezza griglia",
+    step_interval: "intervallo (ms)",
+    mode: "modalità",
+    theme: "tema",
+    controls: "comandi",
+    mode_classic: "classica",
+    mode_wrap: "senza bordi",
+    out_of_range: "deve essere tra {min} e {max}",
+
+    menu_title: "menu",
+    settings_title: "impostazioni",
+    resume: "riprendi",
+    restart: "ricomincia",
+    settings: "impostazioni",
+    quit_to_title: "torna al titolo",
+    exit: "esci",
+
+    controls_hint: "scegli un'azione per assegnarla al prossimo tasto premuto, torna indietro con il tasto menu",
+    press_a_key: "premi un tasto",
+    already_bound: "{key} è già assegnato a {action}",
+    action_up: "muovi su",
+    action_down: "muovi giù",
+    action_left: "muovi a sinistra",
+    action_right: "muovi a destra",
+    action_pause: "pausa",
+    action_confirm: "inizia / seleziona",
+    action_menu: "menu / indietro / esci",
+    action_latency_overlay: "latenza",
+    action_record: "avvia / ferma registrazione",
+
+    hud: "punti {score}  lunghezza {length}  mele {apples}  velocità {speed}ms  tempo {time}  record {best}",
+    stats: "lunghezza {length}  mele {apples}  tempo {time}",
+    paused: "in pausa, premi p per riprendere",
+    game_over: "PARTITA FINITA",
+    victory: "HAI VINTO",
+    your_score: "il tuo punteggio: {score}",
+    hit_wall: "hai sbattuto contro il muro",
+    hit_body: "ti sei morso la coda",
+    filled_board: "il serpente ha riempito tutta la griglia",
+    continue_or_quit: "premi invio per continuare, esc per uscire",
+
+    high_scores: "migliori punteggi",
+    no_scores: "ancora nessun punteggio",
+    back_hint: "torna indietro con il tasto menu",
+    score_settings: "{width}x{height}, {interval}ms, modalità {mode}",
+    new_high_score: "NUOVO RECORD",
+    rank: "numero {rank} dei migliori punteggi",
+    your_initials: "le tue iniziali: {initials}",
+    initials_hint: "scrivile e premi invio, esc per saltare",
+
+    lifetime_stats: "statistiche",
+    games_played: "partite giocate: {games}",
+    apples_eaten: "mele mangiate: {apples}",
+    longest_snake: "serpente più lungo: {length}",
+    time_played: "tempo di gioco: {time}",
+    average_game: "durata media: {time}",
+    game_endings: "come sono finite le partite:",
+    left_unfinished: "hai lasciato prima della fine",
+
+    saved_game: "PARTITA SALVATA",
+    resume_hint: "premi invio per riprenderla, esc per iniziarne una nuova",
+
+    disconnected: "la partita è finita, ricarica la pagina per giocare ancora",
+
+    narrated_title: "rusty rattle. su e giù per scegliere, sinistra e destra per cambiare, invio per iniziare. ",
+    narrated_controls: "comandi. scegli un'azione per assegnarla al prossimo tasto premuto. ",
+    narrated_game_over: "partita finita",
+    narrated_victory: "hai vinto, il serpente riempie la griglia",
+    narrated_end: "{headline}, il tuo punteggio è {score}. lunghezza {length}, {apples} mele, {seconds} secondi. ",
+    narrated_back: "esc per tornare indietro.",
+    heading: "direzione {direction} alla colonna {column} riga {row}. ",
+    apple_at: "mela {position}. ",
+    cells_left: "{cells} a sinistra",
+    cells_right: "{cells} a destra",
+    cells_up: "{cells} in su",
+    cells_down: "{cells} in giù",
+    obstacle_in: "{direction} {obstacle} a {steps}",
+    way_clear: "{direction} libero",
+    apple_eaten: "mela mangiata, lunghezza {length}. ",
+    turned: "girato {direction}. ",
+    danger: "pericolo, {obstacle} davanti a {steps}. ",
+    up: "su",
+    right: "destra",
+    down: "giù",
+    left: "sinistra",
+    wall: "muro",
+    body: "corpo",
+    tail: "coda",
+
+    usage: "uso: rusty-rattle [opzioni]
+
+  --height <celle>       l'altezza della griglia, da 8 a 100
+  --width <celle>        la larghezza della griglia, da 8 a 200
+  --interval-ms <ms>     quanto dura un passo, da 20 a 2000
+  --level <n>            la velocità come livello, da 1 (300ms) a 10 (30ms)
+  --seed <n>             mette le mele sempre negli stessi posti
+  --view <viste>         tui, gui, web o narrated, più di una separate da virgole
+  --mode <modalità>      classic o wrap
+  --theme <nome>         un tema predefinito o uno della cartella dei temi
+  --keymap <file>        i tasti da usare e in cui salvare i cambiamenti
+  --play                 inizia subito una partita invece di mostrare il titolo
+  --write-config         salva le opzioni in uso nel file di configurazione ed esce
+  -h, --help             mostra questo aiuto
+  -V, --version          mostra la versione
+
+la griglia, la velocità, la modalità e il tema si scelgono anche nel titolo,
+quelli dati qui sostituiscono quelli dell'ultima partita.
+le opzioni si leggono prima da $XDG_CONFIG_HOME/rusty-rattle/config.toml,
+poi dalle variabili d'ambiente RUSTY_RATTLE_* e poi dalle opzioni",
+    needs_value: "{flag} richiede un valore",
+    speed_set_twice: "{other} e {flag} impostano entrambi la velocità, usane uno solo",
+    invalid_seed: "seed non valido {seed}: {error}",
+    no_theme: "nessun tema {name}, quelli disponibili sono {names}",
+    unknown_argument: "argomento sconosciuto {argument}",
+    not_in_range: "{flag} deve essere un numero tra {min} e {max}, non {value}",
+};
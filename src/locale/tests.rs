@@ -0,0 +1,84 @@
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+
+use super::{fill, Locale};
+
+//the {names} in a template
+fn placeholders(template: &str) -> BTreeSet<&str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+#[test]
+fn test_parse_locale_names() {
+    assert_eq!("en".parse(), Ok(Locale::English));
+    assert_eq!("it".parse(), Ok(Locale::Italian));
+    assert_eq!("it_IT.UTF-8".parse(), Ok(Locale::Italian));
+    assert_eq!("IT-ch".parse(), Ok(Locale::Italian));
+    assert_eq!("en_GB@euro".parse(), Ok(Locale::English));
+    assert!("fr_FR.UTF-8".parse::<Locale>().is_err());
+    assert!("".parse::<Locale>().is_err());
+}
+
+#[test]
+fn test_locale_from_environment() {
+    let vars = |vars: &'static [(&'static str, &'static str)]| {
+        move |name: &str| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        }
+    };
+    assert_eq!(Locale::from_vars(vars(&[])), Locale::English);
+    assert_eq!(
+        Locale::from_vars(vars(&[("LANG", "it_IT.UTF-8")])),
+        Locale::Italian
+    );
+    //LC_ALL wins over LANG, and empty variables are skipped
+    assert_eq!(
+        Locale::from_vars(vars(&[("LC_ALL", "en_US.UTF-8"), ("LANG", "it_IT.UTF-8")])),
+        Locale::English
+    );
+    assert_eq!(
+        Locale::from_vars(vars(&[("LC_ALL", ""), ("LANG", "it_IT.UTF-8")])),
+        Locale::Italian
+    );
+    //languages without a bundle fall back to English
+    assert_eq!(Locale::from_vars(vars(&[("LANG", "C")])), Locale::English);
+}
+
+#[test]
+fn test_fill_placeholders() {
+    assert_eq!(
+        fill(
+            "must be between {min} and {max}",
+            &[("min", &8), ("max", &100)]
+        ),
+        "must be between 8 and 100"
+    );
+    assert_eq!(fill("{a} {b}", &[("a", &"x")]), "x {b}");
+}
+
+#[test]
+fn test_bundles_have_the_same_placeholders() {
+    let bundle = |locale: Locale| match serde_json::to_value(locale.messages()).unwrap() {
+        Value::Object(messages) => messages,
+        _ => panic!("messages should serialize to an object"),
+    };
+    let english = bundle(Locale::English);
+    for locale in Locale::ALL {
+        for (name, message) in bundle(locale) {
+            let (message, english) = (message.as_str().unwrap(), english[&name].as_str().unwrap());
+            assert!(!message.is_empty(), "{name} is empty in {locale:?}");
+            assert_eq!(
+                placeholders(message),
+                placeholders(english),
+                "{name} in {locale:?}"
+            );
+        }
+    }
+}
@@ -1,70 +1,223 @@
-use std::io::Write;
 use std::sync::mpsc;
-use std::time::Duration;
-use std::{io, thread};
+use std::{env, process, thread};
 
+use crossterm::terminal;
+use rusty_rattle::cli::{self, Command, VERSION};
+use rusty_rattle::config_file;
 use rusty_rattle::controller::Controller;
-use rusty_rattle::event_processes;
+use rusty_rattle::event_processes::{self, TickInterval};
+use rusty_rattle::locale::Locale;
 use rusty_rattle::model::Model;
-use rusty_rattle::{views, Config, ViewType};
+use rusty_rattle::settings::Settings;
+use rusty_rattle::views::export::{ExportOutput, Exporter};
+use rusty_rattle::views::recorder::Recorder;
+use rusty_rattle::{remote, views, Config, ConfigBuilder, ViewType};
 
 fn main() {
-    let config = initialize_config();
+    //the defaults, then the config file, the environment and the command line flags
+    let config = config_from_env(config_file::load(Config::builder()));
+    let usage = config.locale().messages().usage;
+    let config = match cli::parse(env::args().skip(1), config) {
+        Ok(Command::Play(config)) => (*config).build(),
+        Ok(Command::WriteConfig(config)) => {
+            write_config(&(*config).build());
+            return;
+        }
+        Ok(Command::Help) => {
+            println!("{usage}");
+            return;
+        }
+        Ok(Command::Version) => {
+            println!("{VERSION}");
+            return;
+        }
+        Err(e) => {
+            eprintln!("{e}\n\n{usage}");
+            process::exit(2);
+        }
+    };
 
     let (event_sender, event_receiver) = mpsc::channel();
 
-    let view = views::new_view(config);
-    let model = Model::new(view, config);
-    let controller = Controller::new(model);
+    //started before the views, so the recording begins with the title screen
+    let recorder = Recorder::new();
+    if let Some(path) = config.record() {
+        if let Err(e) = recorder.start(path.clone()) {
+            eprintln!("failed to record to {}: {e}", path.display());
+            process::exit(1);
+        }
+    }
+
+    let view = match views::new_view(&config, &recorder, event_sender.clone()) {
+        Ok(view) => view,
+        Err(e) => {
+            eprintln!("failed to start the views: {e}");
+            process::exit(1);
+        }
+    };
+    let view_types = [config.view_type()]
+        .into_iter()
+        .chain(config.mirror_views());
+    if view_types
+        .into_iter()
+        .any(|view_type| *view_type == ViewType::Web)
+    {
+        println!("open http://{} in a browser to play\r", config.web_addr());
+    }
+    let model = Model::new(view, config.clone(), recorder.clone());
+    let tick_interval = TickInterval::new(model.settings().update_interval);
+    let controller = Controller::new(model, tick_interval.clone());
+
+    //kept until the game quits, which removes the unix socket
+    let _remote =
+        config.remote().and_then(
+            |remote| match remote::listen(remote, event_sender.clone()) {
+                Ok(listening) => Some(listening),
+                Err(e) => {
+                    eprintln!("failed to accept remote clients on {remote}: {e}\r");
+                    None
+                }
+            },
+        );
 
     let event_sender_clone1 = event_sender.clone();
     let event_sender_clone2 = event_sender.clone();
-    let handles = [
-        thread::spawn(|| event_processes::event_loop(event_receiver, controller)),
-        thread::spawn(move || event_processes::game_tick_loop(event_sender_clone1, config)),
-        thread::spawn(move || event_processes::input_loop(event_sender_clone2)),
-    ];
+    let event_sender_clone3 = event_sender.clone();
+    let event_loop_handle =
+        thread::spawn(|| event_processes::event_loop(event_receiver, controller));
+    thread::spawn(move || event_processes::game_tick_loop(event_sender_clone1, tick_interval));
+    thread::spawn(move || event_processes::input_loop(event_sender_clone2));
+    thread::spawn(move || event_processes::render_loop(event_sender_clone3));
 
-    for handle in handles {
-        handle.join().unwrap();
+    //the game can also be quit by a remote client, so the input loop
+    //may still be waiting for a key when the event loop returns
+    event_loop_handle.join().unwrap();
+    terminal::disable_raw_mode().unwrap();
+    if let Err(e) = recorder.stop() {
+        eprintln!("failed to save the recording: {e}");
     }
 }
 
-fn initialize_config() -> Config {
-    let stdin = io::stdin();
-
-    let view_type = ViewType::TUI;
-
-    print!("choose the grid height:");
-    io::stdout().flush().expect("failed to flush stdout");
-    let mut grid_height = String::new();
-    stdin
-        .read_line(&mut grid_height)
-        .expect("failed to read line");
-    let grid_height = grid_height.trim().parse().unwrap_or(20);
-
-    print!("choose the grid width:");
-    io::stdout().flush().expect("failed to flush stdout");
-    let mut grid_width = String::new();
-    stdin
-        .read_line(&mut grid_width)
-        .expect("failed to read line");
-    let grid_width = grid_width.trim().parse().unwrap_or(30);
-
-    print!("choose the time interval between steps (in milliseconds):");
-    io::stdout().flush().expect("failed to flush stdout");
-    let mut update_interval = String::new();
-    stdin
-        .read_line(&mut update_interval)
-        .expect("failed to read line");
-    let update_interval = update_interval.trim().parse().unwrap_or(200);
-    let update_interval = Duration::from_millis(update_interval);
-
-    let config = Config::builder()
-        .set_view_type(view_type)
-        .set_grid_height(grid_height)
-        .set_grid_width(grid_width)
-        .set_update_interval(update_interval)
-        .build();
+//grid size, speed, mode and theme are chosen in the title screen menu,
+//or given as command line flags
+fn config_from_env(mut config: ConfigBuilder) -> ConfigBuilder {
+    //the gui view draws every frame into png images instead of the terminal,
+    //more views separated by commas show the same game, e.g. tui,web
+    if let Ok(view_types) = env::var("RUSTY_RATTLE_VIEW") {
+        config = config.clear_mirror_views();
+        for (i, view_type) in view_types.split(',').enumerate() {
+            match (i, view_type.trim().parse()) {
+                (0, Ok(view_type)) => config = config.set_view_type(view_type),
+                (_, Ok(view_type)) => config = config.add_mirror_view(view_type),
+                (_, Err(e)) => eprintln!("{e}"),
+            }
+        }
+    }
+    if let Some(image_output) = env::var_os("RUSTY_RATTLE_IMAGE_OUTPUT") {
+        config = config.set_image_output(image_output.into());
+    }
+
+    //ascii for terminals without unicode support, or emoji
+    if let Ok(glyphs) = env::var("RUSTY_RATTLE_GLYPHS") {
+        match glyphs.parse() {
+            Ok(glyphs) => config = config.set_glyphs(glyphs),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+
+    //half-block or braille to fit big boards in the terminal
+    if let Ok(render_mode) = env::var("RUSTY_RATTLE_RENDER") {
+        match render_mode.parse() {
+            Ok(render_mode) => config = config.set_render_mode(render_mode),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+
+    //how close to the edges the head gets before a big board scrolls, e.g. 5,3
+    if let Ok(dead_zone) = env::var("RUSTY_RATTLE_DEAD_ZONE") {
+        match parse_dead_zone(&dead_zone) {
+            Some(dead_zone) => config = config.set_camera_dead_zone(dead_zone),
+            None => eprintln!("invalid dead zone {dead_zone:?}, expected two numbers like 5,3"),
+        }
+    }
+    if let Ok(minimap) = env::var("RUSTY_RATTLE_MINIMAP") {
+        config = config.set_minimap(matches!(minimap.as_str(), "1" | "true" | "on"));
+    }
+
+    //where the web view serves its page, e.g. 127.0.0.1:8080
+    if let Ok(web_addr) = env::var("RUSTY_RATTLE_WEB_ADDR") {
+        match web_addr.parse() {
+            Ok(web_addr) => config = config.set_web_addr(web_addr),
+            Err(e) => eprintln!("invalid web address {web_addr:?}: {e}"),
+        }
+    }
+
+    //records the terminal view into an asciicast file, like session.cast
+    if let Some(record) = env::var_os("RUSTY_RATTLE_RECORD") {
+        config = config.set_record(record.into());
+    }
+
+    //exports every finished game as game.gif, game.svg for its last frame,
+    //or a directory of svg frames, with the cell size and frame rate chosen
+    if let Some(export) = env::var_os("RUSTY_RATTLE_EXPORT") {
+        let mut exporter = Exporter::new(ExportOutput::from_path(export.into()));
+        if let Ok(cell_size) = env::var("RUSTY_RATTLE_EXPORT_CELL_SIZE") {
+            match cell_size.parse() {
+                Ok(cell_size) => exporter = exporter.with_cell_size(cell_size),
+                Err(e) => eprintln!("invalid export cell size {cell_size:?}: {e}"),
+            }
+        }
+        if let Ok(fps) = env::var("RUSTY_RATTLE_EXPORT_FPS") {
+            match fps.parse() {
+                Ok(fps) => exporter = exporter.with_fps(fps),
+                Err(e) => eprintln!("invalid export frame rate {fps:?}: {e}"),
+            }
+        }
+        config = config.set_export(exporter);
+    }
+
+    //the language of the game, e.g. it, taken from LANG when not set
+    if let Ok(locale) = env::var("RUSTY_RATTLE_LOCALE") {
+        match locale.parse() {
+            Ok(locale) => config = config.set_locale(locale),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+    config = config.fallback_locale(Locale::from_env());
+
+    //where to write the input latency histograms when quitting
+    if let Some(latency_dump) = env::var_os("RUSTY_RATTLE_LATENCY_DUMP") {
+        config = config.set_latency_dump(latency_dump.into());
+    }
+
+    //where to accept remote control clients, e.g. tcp:127.0.0.1:7777
+    if let Ok(remote) = env::var("RUSTY_RATTLE_REMOTE") {
+        match remote.parse() {
+            Ok(remote) => config = config.set_remote(remote),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+
     config
 }
+
+//the options in effect and the settings the title screen would offer
+fn write_config(config: &Config) {
+    let Some(path) = config_file::path() else {
+        eprintln!("no config directory to write to");
+        process::exit(1);
+    };
+    let settings = config.initial_settings(Settings::load_last(config.dirs()));
+    match config_file::save(&path, config, &settings) {
+        Ok(()) => println!("wrote {}", path.display()),
+        Err(e) => {
+            eprintln!("failed to write {}: {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_dead_zone(s: &str) -> Option<(usize, usize)> {
+    let (across, down) = s.split_once(',')?;
+    Some((across.trim().parse().ok()?, down.trim().parse().ok()?))
+}
@@ -1,8 +1,33 @@
-use rand::{self, Rng};
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
-use crate::{views::View, Config};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+use crate::{
+    high_scores::{HighScore, HighScores, ScoreKey, INITIALS_LEN},
+    keymap::Keymap,
+    latency::LatencyTracker,
+    locale::Locale,
+    settings::{GameMode, Settings},
+    stats::Stats,
+    storage::Dirs,
+    views::{recorder::Recorder, View},
+    Config,
+};
+use animation::Animations;
+use controls_menu::ControlsMenu;
+use crossterm::event::KeyCode;
+use in_game_menu::{InGameMenu, InGameMenuItem};
+use render_state::{Board, RenderState, Screen};
+use saved_game::{SavedGame, SAVE_VERSION};
+use title_menu::{MenuInput, TitleMenu};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Orientation {
     Up,
     Right,
@@ -19,16 +44,161 @@ impl Orientation {
                 | (Orientation::Left, Orientation::Right)
         )
     }
+
+    pub fn opposite(&self) -> Orientation {
+        match self {
+            Orientation::Up => Orientation::Down,
+            Orientation::Right => Orientation::Left,
+            Orientation::Down => Orientation::Up,
+            Orientation::Left => Orientation::Right,
+        }
+    }
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GameState {
     TitleScreen,
     Playing,
+    Paused,
+    //the menu opened with esc while playing
+    InGameMenu,
+    //the settings opened from the in-game menu
+    Settings,
+    //the key bindings, opened from the title screen or the settings
+    Controls,
+    //the best scores, opened from the title screen or the settings,
+    //and shown after a new one is saved
+    HighScores,
+    //the lifetime stats, opened from the title screen or the settings
+    Stats,
     GameOver,
+    //the game saved when quitting, offered at launch before the title screen
+    ResumeOffer,
+    //the player chose to close the game
+    Exited,
+}
+
+//what the snake ran into
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeathCause {
+    Wall,
+    Body,
+}
+
+//what remote clients receive after each tick, the snake goes from the head to the tail
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub state: GameState,
+    pub width: usize,
+    pub height: usize,
+    pub score: usize,
+    pub length: usize,
+    pub heading: Option<Orientation>,
+    pub snake: Vec<(usize, usize)>,
+    pub apple: Option<(usize, usize)>,
 }
 
-#[derive(Default, Clone, PartialEq, Debug)]
+impl Snapshot {
+    //the board it shows, to replay the snapshots a remote client saved,
+    //which may have been cut short or edited
+    pub fn grid(&self) -> Result<Vec<Vec<CellContent>>, String> {
+        let (width, height) = (self.width, self.height);
+        if let Some((x, y)) = self
+            .apple
+            .iter()
+            .chain(&self.snake)
+            .find(|(x, y)| *x >= width || *y >= height)
+        {
+            return Err(format!("{x},{y} is outside of the {width}x{height} board"));
+        }
+
+        let mut grid = vec![vec![CellContent::Empty; width]; height];
+        if let Some((x, y)) = self.apple {
+            grid[y][x] = CellContent::Apple;
+        }
+        //from the tail, so a head that ran into the body is drawn over it
+        for (i, &(x, y)) in self.snake.iter().enumerate().rev() {
+            let towards = i
+                .checked_sub(1)
+                .map(|previous| self.direction((x, y), self.snake[previous]));
+            let from = self
+                .snake
+                .get(i + 1)
+                .map(|&next| self.direction(next, (x, y)));
+            grid[y][x] = match (towards, from) {
+                (None, _) => CellContent::Head(self.heading.or(from).unwrap_or(Orientation::Up)),
+                (Some(towards), Some(from)) => CellContent::Body { towards, from },
+                (Some(towards), None) => CellContent::Tail(towards),
+            };
+        }
+        Ok(grid)
+    }
+
+    //the way from a cell to the one next to it, across the edges in wrap mode
+    fn direction(&self, (x, y): (usize, usize), (to_x, to_y): (usize, usize)) -> Orientation {
+        if to_y == y {
+            if to_x == (x + 1) % self.width {
+                Orientation::Right
+            } else {
+                Orientation::Left
+            }
+        } else if to_y == (y + 1) % self.height {
+            Orientation::Down
+        } else {
+            Orientation::Up
+        }
+    }
+}
+
+//the status drawn with every frame
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Hud {
+    pub score: usize,
+    pub length: usize,
+    pub apples: usize,
+    pub tick_interval: Duration,
+    //the time spent playing, without pauses and menus
+    pub elapsed: Duration,
+    //the best score since the game was launched
+    pub best_score: usize,
+}
+
+const INITIAL_SNAKE_LEN: usize = 4;
+
+//the coordinates of the cell next to (x, y) in the given direction,
+//None if the snake would hit a wall
+pub fn neighbour(
+    mode: GameMode,
+    (width, height): (usize, usize),
+    (x, y): (usize, usize),
+    direction: Orientation,
+) -> Option<(usize, usize)> {
+    match mode {
+        GameMode::Classic => match direction {
+            Orientation::Up => y.checked_sub(1).map(|y| (x, y)),
+            Orientation::Right => (x + 1 < width).then_some((x + 1, y)),
+            Orientation::Down => (y + 1 < height).then_some((x, y + 1)),
+            Orientation::Left => x.checked_sub(1).map(|x| (x, y)),
+        },
+        GameMode::Wrap => Some(match direction {
+            Orientation::Up => (x, (y + height - 1) % height),
+            Orientation::Right => ((x + 1) % width, y),
+            Orientation::Down => (x, (y + 1) % height),
+            Orientation::Left => ((x + width - 1) % width, y),
+        }),
+    }
+}
+
+//an apple is worth 10 points at the default speed of 200ms,
+//and more the faster the snake goes
+fn points_per_apple(update_interval: Duration) -> usize {
+    (2000 / update_interval.as_millis().max(1) as usize).max(1)
+}
+
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CellContent {
     #[default]
     Empty,
@@ -44,32 +214,121 @@ pub enum CellContent {
 pub struct Model {
     view: Box<dyn View>,
     game_state: GameState,
+    settings: Settings,
+    title_menu: TitleMenu,
+    in_game_menu: InGameMenu,
+    controls_menu: ControlsMenu,
+    keymap: Keymap,
     snake_len: usize,
+    apples: usize,
+    score: usize,
+    best_score: usize,
+    ticks: u64,
     grid: Vec<Vec<CellContent>>,
     grid_height: usize,
     grid_width: usize,
+    latency: LatencyTracker,
+    latency_overlay: bool,
+    latency_dump: Option<PathBuf>,
+    animations: Animations,
+    //None until the snake dies, and when it filled the whole board
+    death_cause: Option<DeathCause>,
+    recorder: Recorder,
+    locale: Locale,
+    //where rebound keys are saved, the user's config when None
+    keymap_file: Option<PathBuf>,
+    //places the apples, saved with the game so they keep coming in the same places
+    rng: ChaCha12Rng,
+    high_scores: HighScores,
+    //Some while the player types the initials of a new high score
+    initials: Option<String>,
+    //the table shown in the high scores screen
+    high_scores_key: ScoreKey,
+    //shown in the stats screen, read again every time it opens
+    stats: Stats,
+    //where to go back to from the high scores and the stats screens
+    screen_return: GameState,
+    //where the files of the player are read and written
+    dirs: Dirs,
 }
 
 impl Model {
-    pub fn new(view: Box<dyn View>, config: Config) -> Self {
-        let grid = vec![vec![CellContent::Empty; config.grid_width]; config.grid_height];
-
-        //print title screen
-        view.draw_title_screen();
+    //the recorder is toggled with its key while playing
+    pub fn new(view: Box<dyn View>, config: Config, recorder: Recorder) -> Self {
+        //offer the settings of the last game, if there was one
+        let dirs = config.dirs().clone();
+        let settings = config.initial_settings(Settings::load_last(&dirs));
+        let title_menu = TitleMenu::new(&settings);
+        let keymap = match &config.keymap {
+            Some(path) => Keymap::load_or_set_aside(path),
+            None => Keymap::load(&dirs),
+        }
+        .unwrap_or_else(|e| {
+            eprintln!("failed to read the key bindings: {e}\r");
+            Keymap::default()
+        });
+        let grid = vec![vec![CellContent::Empty; settings.grid_width]; settings.grid_height];
+        let locale = config.locale();
+        let rng = match config.seed() {
+            Some(seed) => ChaCha12Rng::seed_from_u64(seed),
+            None => ChaCha12Rng::from_entropy(),
+        };
+        let play = config.play();
+        let high_scores_key = ScoreKey::new(&settings);
 
-        Model {
+        let mut model = Model {
             view,
             game_state: GameState::TitleScreen,
+            title_menu,
+            in_game_menu: InGameMenu::default(),
+            controls_menu: ControlsMenu::new(GameState::TitleScreen),
+            keymap,
             grid,
-            grid_width: config.grid_width,
-            grid_height: config.grid_height,
-            snake_len: 4,
+            grid_width: settings.grid_width,
+            grid_height: settings.grid_height,
+            settings,
+            snake_len: INITIAL_SNAKE_LEN,
+            apples: 0,
+            score: 0,
+            best_score: 0,
+            ticks: 0,
+            latency: LatencyTracker::default(),
+            latency_overlay: false,
+            latency_dump: config.latency_dump,
+            animations: Animations::default(),
+            death_cause: None,
+            recorder,
+            locale,
+            keymap_file: config.keymap,
+            rng,
+            high_scores: HighScores::load(&dirs).unwrap_or_else(|e| {
+                eprintln!("failed to read the high scores: {e}\r");
+                HighScores::default()
+            }),
+            initials: None,
+            high_scores_key,
+            stats: Stats::default(),
+            screen_return: GameState::TitleScreen,
+            dirs,
+        };
+
+        //print title screen
+        model.view.set_theme(&model.settings.theme);
+        model.view.set_locale(model.locale);
+        model.render();
+        if play {
+            model.start_game();
+        } else {
+            model.offer_saved_game();
         }
+        model
     }
 
-    //returns the game state after the update
-    pub fn update(&mut self, input_direction: Option<Orientation>) -> GameState {
+    //returns the game state after the update,
+    //the input comes with the instant its key was read
+    pub fn update(&mut self, input: Option<(Orientation, Instant)>) -> GameState {
         let new_game_state = GameState::Playing;
+        self.ticks += 1;
 
         let (head_x, head_y) = self.head_coordinates();
 
@@ -78,37 +337,30 @@ impl Model {
             .expect("head should be inside grid");
 
         //cannot change to the opposite direction instantly
-        let mut input_direction = input_direction;
+        let mut input = input;
         if let CellContent::Head(prev_direction) = head {
-            if let Some(new_direction) = input_direction {
+            if let Some((new_direction, _)) = input {
                 if new_direction.is_opposite(*prev_direction) {
-                    input_direction = None;
+                    input = None;
                 }
             }
         }
+        let input_direction = input.map(|(direction, _)| direction);
+        let input_read_at = input.map(|(_, read_at)| read_at);
 
         //get the cell in front of the head
-        let front_coordinates: Option<(usize, usize)> = match input_direction {
-            Some(Orientation::Up) => head_y.checked_sub(1).map(|y| (head_x, y)),
-            Some(Orientation::Down) => Some((head_x, head_y + 1)),
-            Some(Orientation::Left) => head_x.checked_sub(1).map(|x| (x, head_y)),
-            Some(Orientation::Right) => Some((head_x + 1, head_y)),
-
+        let walking_direction = match (input_direction, head) {
+            (Some(direction), _) => direction,
             //if there is no input in the buffer, maintain the previous input_direction
-            None => match head {
-                CellContent::Head(Orientation::Up) => head_y.checked_sub(1).map(|y| (head_x, y)),
-                CellContent::Head(Orientation::Down) => Some((head_x, head_y + 1)),
-                CellContent::Head(Orientation::Left) => head_x.checked_sub(1).map(|x| (x, head_y)),
-                CellContent::Head(Orientation::Right) => Some((head_x + 1, head_y)),
-
-                _ => panic!("expected head"),
-            },
+            (None, CellContent::Head(direction)) => *direction,
+            _ => panic!("expected head"),
         };
+        let front_coordinates = self.neighbour((head_x, head_y), walking_direction);
 
         let (front_x, front_y) = match front_coordinates {
             Some(_) => front_coordinates.unwrap(),
             None => {
-                self.game_over();
+                self.game_over(DeathCause::Wall);
                 return GameState::GameOver;
             }
         };
@@ -116,7 +368,7 @@ impl Model {
         let front = match self.get_cell(front_x, front_y) {
             //wall collision => game over
             None => {
-                self.game_over();
+                self.game_over(DeathCause::Wall);
                 return GameState::GameOver;
             }
             Some(h) => h,
@@ -131,18 +383,31 @@ impl Model {
                 towards: _,
                 from: _,
             } => {
-                self.game_over();
+                self.game_over(DeathCause::Body);
                 return GameState::GameOver;
             }
             CellContent::Apple => {
                 self.advance_snake(input_direction, true);
                 self.snake_len += 1;
+                self.apples += 1;
+                self.score += points_per_apple(self.settings.update_interval);
+                self.best_score = self.best_score.max(self.score);
+                self.animations.pulse((front_x, front_y));
+                //there is no room left for another apple
+                if self.snake_len == self.grid_width * self.grid_height {
+                    self.win();
+                    return GameState::GameOver;
+                }
                 self.spawn_apple()
             }
         }
 
+        if let Some(read_at) = input_read_at {
+            self.latency.record_apply(read_at);
+        }
+
         //if the game is still going update the view
-        self.draw_grid_on_view();
+        self.draw_grid_on_view(input_read_at);
 
         new_game_state
     }
@@ -248,21 +513,17 @@ impl Model {
         tail_x: usize,
         tail_y: usize,
     ) -> (Option<&mut CellContent>, usize, usize) {
-        let (next_x, next_y) = match cell_iter {
+        let orientation = match cell_iter {
             CellContent::Body {
                 towards: orientation,
                 from: _,
             }
             | CellContent::Head(orientation)
-            | CellContent::Tail(orientation) => match orientation {
-                Orientation::Up => (tail_x, tail_y - 1),
-                Orientation::Right => (tail_x + 1, tail_y),
-                Orientation::Down => (tail_x, tail_y + 1),
-                Orientation::Left => (tail_x - 1, tail_y),
-            },
-            _ => {
-                panic!("expected snake cell")
-            }
+            | CellContent::Tail(orientation) => *orientation,
+            _ => panic!("expected snake cell"),
+        };
+        let Some((next_x, next_y)) = self.neighbour((tail_x, tail_y), orientation) else {
+            return (None, tail_x, tail_y);
         };
 
         let next_cell = self.get_cell_mut(next_x, next_y);
@@ -276,30 +537,36 @@ impl Model {
         tail_x: usize,
         tail_y: usize,
     ) -> (Option<&CellContent>, usize, usize) {
-        let (next_x, next_y) = match cell_iter {
+        let orientation = match cell_iter {
             CellContent::Body {
                 towards: orientation,
                 from: _,
             }
             | CellContent::Head(orientation)
-            | CellContent::Tail(orientation) => match orientation {
-                Orientation::Up => (tail_x, tail_y - 1),
-                Orientation::Right => (tail_x + 1, tail_y),
-                Orientation::Down => (tail_x, tail_y + 1),
-                Orientation::Left => (tail_x - 1, tail_y),
-            },
+            | CellContent::Tail(orientation) => *orientation,
             _ => panic!("expected snake cell"),
         };
+        let Some((next_x, next_y)) = self.neighbour((tail_x, tail_y), orientation) else {
+            return (None, tail_x, tail_y);
+        };
 
         let next_cell = self.get_cell(next_x, next_y);
 
         (next_cell, next_x, next_y)
     }
 
+    fn neighbour(&self, cell: (usize, usize), direction: Orientation) -> Option<(usize, usize)> {
+        neighbour(
+            self.settings.mode,
+            (self.grid_width, self.grid_height),
+            cell,
+            direction,
+        )
+    }
+
     fn spawn_apple(&mut self) {
         let available_cells = self.grid_width * self.grid_height - self.snake_len;
-        let mut rng = rand::thread_rng();
-        let index = rng.gen_range(0..available_cells);
+        let index = self.rng.gen_range(0..available_cells);
 
         //grab the indexth empty cell and put an apple there
         *self
@@ -313,21 +580,614 @@ impl Model {
 
     pub fn start_game(&mut self) {
         match self.game_state {
-            GameState::TitleScreen | GameState::GameOver => {
-                Self::initialize_grid(&mut self.grid, (self.grid_width, self.grid_height));
-                self.game_state = GameState::Playing;
+            GameState::TitleScreen => {
+                //invalid settings are already shown as errors in the menu
+                if let Some(settings) = self.title_menu.settings() {
+                    self.new_game(settings);
+                }
             }
+            //a new high score waits for the initials first
+            GameState::GameOver if self.initials.is_none() => self.start_round(),
             _ => {}
         }
     }
 
-    fn game_over(&mut self) {
+    fn new_game(&mut self, settings: Settings) {
+        //remembering the settings for the next run is not essential to play
+        let _ = settings.save_last(&self.dirs);
+        self.apply_settings(settings);
+        self.start_round();
+    }
+
+    //a fresh snake with the current settings
+    fn start_round(&mut self) {
+        Self::initialize_grid(&mut self.grid, (self.grid_width, self.grid_height));
+        self.snake_len = INITIAL_SNAKE_LEN;
+        self.apples = 0;
+        self.score = 0;
+        self.ticks = 0;
+        self.animations.clear();
+        self.death_cause = None;
+        self.initials = None;
+        self.game_state = GameState::Playing;
+    }
+
+    pub fn menu_input(&mut self, input: MenuInput) {
+        match (self.game_state, input) {
+            (GameState::TitleScreen, MenuInput::Select) if self.title_menu.controls_selected() => {
+                self.open_controls()
+            }
+            (GameState::TitleScreen, MenuInput::Select)
+                if self.title_menu.high_scores_selected() =>
+            {
+                self.open_high_scores(self.menu_score_key())
+            }
+            (GameState::TitleScreen, MenuInput::Select) if self.title_menu.stats_selected() => {
+                self.open_stats()
+            }
+            (GameState::TitleScreen, MenuInput::Select) => self.start_game(),
+            (GameState::TitleScreen, MenuInput::Back) => self.exit(),
+            (GameState::TitleScreen, _) => {
+                self.title_menu.handle_input(input);
+                self.render();
+            }
+
+            (GameState::InGameMenu, MenuInput::Select) => match self.in_game_menu.selected_item() {
+                InGameMenuItem::Resume => self.resume(),
+                InGameMenuItem::Restart => {
+                    self.record_abandoned_game();
+                    //settings changed in the in-game menu are used from the restart on
+                    let settings = self
+                        .title_menu
+                        .settings()
+                        .unwrap_or_else(|| self.settings.clone());
+                    self.new_game(settings);
+                }
+                InGameMenuItem::Settings => {
+                    self.game_state = GameState::Settings;
+                    self.render();
+                }
+                InGameMenuItem::QuitToTitle => {
+                    self.record_abandoned_game();
+                    self.game_state = GameState::TitleScreen;
+                    self.title_menu = TitleMenu::new(&self.settings);
+                    self.render();
+                }
+                InGameMenuItem::Exit => self.exit(),
+            },
+            (GameState::InGameMenu, MenuInput::Back) => self.resume(),
+            (GameState::InGameMenu, _) => {
+                self.in_game_menu.handle_input(input);
+                self.render();
+            }
+
+            (GameState::Settings, MenuInput::Select) if self.title_menu.controls_selected() => {
+                self.open_controls()
+            }
+            (GameState::Settings, MenuInput::Select) if self.title_menu.high_scores_selected() => {
+                self.open_high_scores(self.menu_score_key())
+            }
+            (GameState::Settings, MenuInput::Select) if self.title_menu.stats_selected() => {
+                self.open_stats()
+            }
+            //the settings cannot be left while some of them are invalid
+            (GameState::Settings, MenuInput::Select | MenuInput::Back) => {
+                if self.title_menu.settings().is_some() {
+                    self.game_state = GameState::InGameMenu;
+                }
+                self.render();
+            }
+            (GameState::Settings, _) => {
+                self.title_menu.handle_input(input);
+                self.render();
+            }
+
+            (GameState::Controls, MenuInput::Back) => {
+                self.game_state = self.controls_menu.return_to();
+                self.render();
+            }
+            (GameState::Controls, _) => {
+                self.controls_menu.handle_input(input);
+                self.render();
+            }
+
+            (GameState::HighScores | GameState::Stats, MenuInput::Select | MenuInput::Back) => {
+                self.game_state = self.screen_return;
+                self.render();
+            }
+            (GameState::HighScores | GameState::Stats, _) => {}
+
+            //the initials of a new high score, once the dead snake has dissolved
+            (GameState::GameOver, _) if self.initials.is_none() || self.animations.is_dying() => {}
+            (GameState::GameOver, MenuInput::Select) => self.save_high_score(),
+            (GameState::GameOver, MenuInput::Backspace) => {
+                if let Some(initials) = &mut self.initials {
+                    initials.pop();
+                }
+                self.render();
+            }
+            (GameState::GameOver, MenuInput::Back) => {
+                self.initials = None;
+                self.render();
+            }
+            (GameState::GameOver, _) => {}
+
+            (GameState::ResumeOffer, MenuInput::Select) => self.resume_saved_game(),
+            (GameState::ResumeOffer, MenuInput::Back) => self.discard_saved_game(),
+            (GameState::ResumeOffer, _) => {}
+
+            (GameState::Playing | GameState::Paused | GameState::Exited, _) => {}
+        }
+    }
+
+    //the table of the settings chosen in the menu, or of the last game while they are invalid
+    fn menu_score_key(&self) -> ScoreKey {
+        match self.title_menu.settings() {
+            Some(settings) => ScoreKey::new(&settings),
+            None => ScoreKey::new(&self.settings),
+        }
+    }
+
+    fn open_high_scores(&mut self, key: ScoreKey) {
+        self.high_scores_key = key;
+        self.screen_return = self.game_state;
+        self.game_state = GameState::HighScores;
+        self.render();
+    }
+
+    fn open_stats(&mut self) {
+        self.stats = Stats::load(&self.dirs).unwrap_or_else(|e| {
+            eprintln!("failed to read the stats: {e}\r");
+            Stats::default()
+        });
+        self.screen_return = self.game_state;
+        self.game_state = GameState::Stats;
+        self.render();
+    }
+
+    //adds the game that just ended to the lifetime stats
+    fn record_stats(&self) {
+        let cause = self.death_cause;
+        self.update_stats(|stats, apples, length, play_time| {
+            stats.record_game(apples, length, play_time, cause)
+        });
+    }
+
+    //a game restarted, left for the title screen or thrown away when offered
+    //counts too, the one saved when exiting is counted once it ends
+    fn record_abandoned_game(&self) {
+        self.update_stats(Stats::record_abandoned_game);
+    }
+
+    fn update_stats(&self, record: impl FnOnce(&mut Stats, usize, usize, Duration)) {
+        //another game may have saved its stats since this one started,
+        //and a file that can't be read must not be saved over
+        let mut stats = match Stats::load(&self.dirs) {
+            Ok(stats) => stats,
+            Err(e) => {
+                eprintln!("failed to read the stats, the game is not added to them: {e}\r");
+                return;
+            }
+        };
+        record(&mut stats, self.apples, self.snake_len, self.hud().elapsed);
+        //the game goes on without them if they cannot be saved
+        let _ = stats.save(&self.dirs);
+    }
+
+    pub fn is_entering_initials(&self) -> bool {
+        self.game_state == GameState::GameOver && self.initials.is_some()
+    }
+
+    pub fn type_initial(&mut self, c: char) {
+        if self.animations.is_dying() || !c.is_ascii_alphanumeric() {
+            return;
+        }
+        if let Some(initials) = &mut self.initials {
+            if initials.len() < INITIALS_LEN {
+                initials.push(c.to_ascii_uppercase());
+                self.render();
+            }
+        }
+    }
+
+    //saves the score under the initials typed and shows where it placed,
+    //going back to the game over panel from there
+    fn save_high_score(&mut self) {
+        let Some(initials) = self.initials.take_if(|initials| !initials.is_empty()) else {
+            return;
+        };
+        let key = ScoreKey::new(&self.settings);
+        //another game may have saved scores since this one started,
+        //and a file that can't be read must not be saved over
+        let loaded = match HighScores::load(&self.dirs) {
+            Ok(high_scores) => {
+                self.high_scores = high_scores;
+                true
+            }
+            Err(e) => {
+                eprintln!("failed to read the high scores, the new one is not saved: {e}\r");
+                false
+            }
+        };
+        self.high_scores.insert(
+            &key,
+            HighScore {
+                initials,
+                score: self.score,
+            },
+        );
+        //the score still shows in the table for this run if it cannot be saved
+        if loaded {
+            let _ = self.high_scores.save(&self.dirs);
+        }
+        self.open_high_scores(key);
+    }
+
+    //a score good enough for the table asks for the initials of the player
+    fn check_high_score(&mut self) {
+        let rank = self
+            .high_scores
+            .rank(&ScoreKey::new(&self.settings), self.score);
+        self.initials = rank.map(|_| String::new());
+    }
+
+    fn open_controls(&mut self) {
+        self.controls_menu = ControlsMenu::new(self.game_state);
+        self.game_state = GameState::Controls;
+        self.render();
+    }
+
+    //binds the key to the action selected in the controls screen
+    pub fn bind_key(&mut self, key: KeyCode) {
+        if self.game_state == GameState::Controls && self.controls_menu.is_capturing() {
+            if self
+                .controls_menu
+                .bind(key, &mut self.keymap, self.locale.messages())
+            {
+                //the new binding still works for this run if it cannot be saved
+                let _ = match &self.keymap_file {
+                    Some(path) => self.keymap.save_file(path),
+                    None => self.keymap.save(&self.dirs),
+                };
+            }
+            self.render();
+        }
+    }
+
+    pub fn is_capturing_key(&self) -> bool {
+        self.game_state == GameState::Controls && self.controls_menu.is_capturing()
+    }
+
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    pub fn open_menu(&mut self) {
+        if let GameState::Playing | GameState::Paused = self.game_state {
+            self.game_state = GameState::InGameMenu;
+            self.in_game_menu = InGameMenu::default();
+            self.render();
+        }
+    }
+
+    pub fn exit(&mut self) {
+        self.save_game();
+        self.game_state = GameState::Exited;
+    }
+
+    //a game left halfway through, from the board or from any menu opened over it
+    fn is_game_in_progress(&self) -> bool {
+        match self.game_state {
+            GameState::Playing
+            | GameState::Paused
+            | GameState::InGameMenu
+            | GameState::Settings => true,
+            GameState::Controls => self.controls_menu.return_to() == GameState::Settings,
+            GameState::HighScores | GameState::Stats => self.screen_return == GameState::Settings,
+            GameState::TitleScreen
+            | GameState::GameOver
+            | GameState::ResumeOffer
+            | GameState::Exited => false,
+        }
+    }
+
+    //keeps the game in progress when quitting, to offer it at the next launch
+    pub fn save_game(&self) {
+        if !self.is_game_in_progress() {
+            return;
+        }
+        let saved_game = SavedGame {
+            version: SAVE_VERSION,
+            settings: self.settings.clone(),
+            grid: self.grid.clone(),
+            snake_len: self.snake_len,
+            apples: self.apples,
+            score: self.score,
+            ticks: self.ticks,
+            rng: self.rng.clone(),
+        };
+        if let Err(e) = saved_game.save(&self.dirs) {
+            eprintln!("failed to save the game: {e}\r");
+        }
+    }
+
+    //shows the saved game under a panel asking whether to resume it,
+    //a save that cannot be resumed is reported and thrown away
+    fn offer_saved_game(&mut self) {
+        let saved_game = match SavedGame::load(&self.dirs) {
+            Ok(Some(saved_game)) => saved_game,
+            Ok(None) => return,
+            Err(e) => {
+                eprintln!("the saved game cannot be resumed: {e}\r");
+                let _ = SavedGame::remove(&self.dirs);
+                return;
+            }
+        };
+        self.apply_settings(saved_game.settings);
+        self.grid = saved_game.grid;
+        self.snake_len = saved_game.snake_len;
+        self.apples = saved_game.apples;
+        self.score = saved_game.score;
+        self.best_score = saved_game.score;
+        self.ticks = saved_game.ticks;
+        self.rng = saved_game.rng;
+        self.game_state = GameState::ResumeOffer;
+        self.render();
+    }
+
+    //the saved game goes on paused, so the player has time to find the snake
+    fn resume_saved_game(&mut self) {
+        //a game is saved only once, quitting it again saves it anew
+        let _ = SavedGame::remove(&self.dirs);
+        self.title_menu = TitleMenu::new(&self.settings);
+        self.game_state = GameState::Paused;
+        self.render();
+    }
+
+    fn discard_saved_game(&mut self) {
+        let _ = SavedGame::remove(&self.dirs);
+        self.record_abandoned_game();
+        //the title screen offers the settings it was going to before the saved game
+        if let Some(settings) = self.title_menu.settings() {
+            self.apply_settings(settings);
+        }
+        self.best_score = 0;
+        self.game_state = GameState::TitleScreen;
+        self.render();
+    }
+
+    fn resume(&mut self) {
+        self.game_state = GameState::Playing;
+        self.draw_grid_on_view(None);
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    fn apply_settings(&mut self, settings: Settings) {
+        self.grid_width = settings.grid_width;
+        self.grid_height = settings.grid_height;
+        self.grid = vec![vec![CellContent::Empty; settings.grid_width]; settings.grid_height];
+        self.view.set_theme(&settings.theme);
+        self.settings = settings;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        match self.game_state {
+            GameState::Playing => {
+                self.game_state = GameState::Paused;
+                self.render();
+            }
+            GameState::Paused => self.resume(),
+            GameState::TitleScreen
+            | GameState::InGameMenu
+            | GameState::Settings
+            | GameState::Controls
+            | GameState::HighScores
+            | GameState::Stats
+            | GameState::GameOver
+            | GameState::ResumeOffer
+            | GameState::Exited => {}
+        }
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        //there is no snake on the grid before the first game, whatever screen is open
+        let has_snake = self
+            .grid
+            .iter()
+            .flatten()
+            .any(|cell| matches!(cell, CellContent::Tail(_)));
+        let snake = match has_snake {
+            true => self.snake_coordinates().into_iter().rev().collect(),
+            false => Vec::new(),
+        };
+        let heading = snake.first().and_then(|&(x, y)| match self.get_cell(x, y) {
+            Some(CellContent::Head(orientation)) => Some(*orientation),
+            _ => None,
+        });
+        let apple = self.grid.iter().enumerate().find_map(|(y, row)| {
+            row.iter()
+                .position(|cell| *cell == CellContent::Apple)
+                .map(|x| (x, y))
+        });
+
+        Snapshot {
+            state: self.game_state,
+            width: self.grid_width,
+            height: self.grid_height,
+            score: self.score,
+            length: self.snake_len,
+            heading,
+            snake,
+            apple,
+        }
+    }
+
+    //the game over panel is drawn once the snake has dissolved
+    fn game_over(&mut self, cause: DeathCause) {
+        self.game_state = GameState::GameOver;
+        self.death_cause = Some(cause);
+        self.record_stats();
+        self.check_high_score();
+        let snake = self.snake_coordinates().into_iter().rev().collect();
+        self.animations.die(snake);
+    }
+
+    //called by the render clock, much more often than the game ticks
+    pub fn animate(&mut self) {
+        if !self.animations.is_running() {
+            return;
+        }
+        match self.game_state {
+            GameState::Playing => {
+                self.animations.advance();
+                self.draw_grid_on_view(None);
+            }
+            GameState::GameOver => {
+                self.animations.advance();
+                self.render();
+            }
+            //the animations wait under the menus and the pause
+            GameState::TitleScreen
+            | GameState::Paused
+            | GameState::InGameMenu
+            | GameState::Settings
+            | GameState::Controls
+            | GameState::HighScores
+            | GameState::Stats
+            | GameState::ResumeOffer
+            | GameState::Exited => {}
+        }
+    }
+
+    pub fn hud(&self) -> Hud {
+        //the game clock only moves with the ticks, so it stops while paused;
+        //it stops at its largest instead of wrapping around in a very long game
+        Hud {
+            score: self.score,
+            length: self.snake_len,
+            apples: self.apples,
+            tick_interval: self.settings.update_interval,
+            elapsed: self
+                .settings
+                .update_interval
+                .saturating_mul(u32::try_from(self.ticks).unwrap_or(u32::MAX)),
+            best_score: self.best_score,
+        }
+    }
+
+    fn win(&mut self) {
         self.game_state = GameState::GameOver;
-        self.view.draw_game_over(self.snake_len);
+        self.death_cause = None;
+        self.record_stats();
+        self.check_high_score();
+        self.animations.clear();
+        self.render();
+    }
+
+    //the board as it is drawn, with the animations running on it
+    fn board(&self) -> Board {
+        let mut grid = self.grid.clone();
+        self.animations.apply(&mut grid);
+        Board {
+            grid,
+            hud: self.hud(),
+            effects: self.animations.effects(),
+            mode: self.settings.mode,
+        }
+    }
+
+    pub fn render_state(&self) -> RenderState {
+        let messages = self.locale.messages();
+        let screen = match self.game_state {
+            GameState::TitleScreen | GameState::Exited => Screen::Title {
+                menu: self.title_menu.entries(messages),
+                controls: self.keymap.help(messages),
+            },
+            GameState::Controls => Screen::Controls {
+                bindings: self.controls_menu.entries(&self.keymap, messages),
+                message: self.controls_menu.message().map(String::from),
+            },
+            GameState::Playing => Screen::Playing(self.board()),
+            GameState::Paused => Screen::Paused(self.board()),
+            GameState::InGameMenu => Screen::Menu {
+                board: self.board(),
+                title: messages.menu_title,
+                entries: self.in_game_menu.entries(messages),
+            },
+            GameState::Settings => Screen::Menu {
+                board: self.board(),
+                title: messages.settings_title,
+                entries: self.title_menu.entries(messages),
+            },
+            //the panel waits for the dead snake to dissolve
+            GameState::GameOver if self.animations.is_dying() => Screen::Playing(self.board()),
+            GameState::GameOver => match (&self.initials, self.death_cause) {
+                (Some(initials), _) => Screen::NewHighScore {
+                    board: self.board(),
+                    rank: self
+                        .high_scores
+                        .rank(&ScoreKey::new(&self.settings), self.score)
+                        .unwrap_or_default(),
+                    initials: initials.clone(),
+                },
+                (None, Some(cause)) => Screen::GameOver {
+                    board: self.board(),
+                    cause,
+                },
+                (None, None) => Screen::Victory(self.board()),
+            },
+            GameState::HighScores => Screen::HighScores {
+                settings: self.high_scores_key.describe(messages),
+                scores: self.high_scores.scores(&self.high_scores_key).to_vec(),
+            },
+            GameState::Stats => Screen::Stats(self.stats.clone()),
+            GameState::ResumeOffer => Screen::ResumeOffer(self.board()),
+        };
+        let mut state = RenderState::new(screen);
+        if self.latency_overlay && state.board().is_some() {
+            state.debug = Some(self.latency.overlay_lines());
+        }
+        state
     }
 
-    fn draw_grid_on_view(&self) {
-        self.view.draw_frame(self.grid.clone());
+    fn render(&self) {
+        self.view.render(&self.render_state());
+    }
+
+    fn draw_grid_on_view(&mut self, input_read_at: Option<Instant>) {
+        self.render();
+        //render returns once the frame is flushed to the output
+        if let Some(read_at) = input_read_at {
+            self.latency.record_pixel(read_at);
+        }
+    }
+
+    pub fn toggle_latency_overlay(&mut self) {
+        self.latency_overlay = !self.latency_overlay;
+    }
+
+    //starts recording to a new file in the current directory, or saves the running recording
+    pub fn toggle_recording(&mut self) {
+        let result = if self.recorder.is_recording() {
+            self.recorder.stop().map(|_| ())
+        } else {
+            self.recorder.start(Recorder::timestamped_path())
+        };
+        if let Err(e) = result {
+            eprintln!("failed to record the session: {e}\r");
+        }
+    }
+
+    //writes the latency histograms to the dump file, if one is configured
+    pub fn dump_latency(&self) {
+        if let Some(path) = &self.latency_dump {
+            if let Err(e) = self.latency.dump_to_file(path) {
+                eprintln!("failed to write latency dump to {}: {e}\r", path.display());
+            }
+        }
     }
 
     pub fn game_state(&self) -> GameState {
@@ -339,10 +1199,7 @@ impl Model {
             .get_cell_mut(x_tail, y_tail)
             .expect("cell should be within grid") = CellContent::Empty;
     }
-    fn initialize_grid(
-        grid: &mut Vec<Vec<CellContent>>,
-        (grid_width, grid_height): (usize, usize),
-    ) {
+    fn initialize_grid(grid: &mut [Vec<CellContent>], (grid_width, grid_height): (usize, usize)) {
         //initialize every cell as empty
         grid.iter_mut()
             .flat_map(|row| row.iter_mut())
@@ -365,4 +1222,10 @@ impl Model {
     }
 }
 
+pub mod animation;
 mod cell_content_iterator;
+pub mod controls_menu;
+pub mod in_game_menu;
+pub mod render_state;
+pub mod saved_game;
+pub mod title_menu;
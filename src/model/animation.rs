@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use super::CellContent;
+
+//the time between two frames of the animations, whatever the speed of the game
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(40);
+
+//the dead snake blinks for these frames, then dissolves
+const FLASH_FRAMES: usize = 6;
+//long snakes lose more than a segment per frame so the panel does not wait too long
+const DISSOLVE_FRAMES: usize = 20;
+const PULSE_FRAMES: usize = 6;
+
+//something drawn over the board for a few frames
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Effect {
+    //a ring around the cell an apple was eaten in, growing with every frame
+    Pulse { cell: (usize, usize), radius: usize },
+}
+
+impl Effect {
+    //the empty cells of the grid the effect is drawn in
+    pub fn cells(&self, grid: &[Vec<CellContent>]) -> Vec<(usize, usize)> {
+        match *self {
+            Effect::Pulse {
+                cell: (x, y),
+                radius,
+            } => {
+                let ys = y.saturating_sub(radius)..=y + radius;
+                ys.flat_map(|ry| (x.saturating_sub(radius)..=x + radius).map(move |rx| (rx, ry)))
+                    .filter(|&(rx, ry)| rx.abs_diff(x).max(ry.abs_diff(y)) == radius)
+                    .filter(|&(rx, ry)| {
+                        grid.get(ry).and_then(|row| row.get(rx)) == Some(&CellContent::Empty)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+struct Death {
+    //from the head to the tail
+    snake: Vec<(usize, usize)>,
+    frame: usize,
+}
+
+impl Death {
+    fn segments_per_frame(&self) -> usize {
+        self.snake.len().div_ceil(DISSOLVE_FRAMES).max(1)
+    }
+
+    fn frames(&self) -> usize {
+        FLASH_FRAMES + self.snake.len().div_ceil(self.segments_per_frame())
+    }
+
+    fn is_over(&self) -> bool {
+        self.frame >= self.frames()
+    }
+
+    //the segments missing from the current frame, the whole snake every
+    //other frame while it blinks, then more of it from the head on
+    fn hidden(&self) -> &[(usize, usize)] {
+        let hidden = match self.frame.checked_sub(FLASH_FRAMES) {
+            None if self.frame % 2 == 1 => self.snake.len(),
+            None => 0,
+            Some(frame) => (frame + 1) * self.segments_per_frame(),
+        };
+        &self.snake[..hidden.min(self.snake.len())]
+    }
+}
+
+struct Pulse {
+    cell: (usize, usize),
+    frame: usize,
+}
+
+//the animations running on the render clock, separately from the game ticks
+#[derive(Default)]
+pub struct Animations {
+    death: Option<Death>,
+    pulses: Vec<Pulse>,
+}
+
+impl Animations {
+    pub fn pulse(&mut self, cell: (usize, usize)) {
+        self.pulses.push(Pulse { cell, frame: 0 });
+    }
+
+    //the snake goes from the head to the tail
+    pub fn die(&mut self, snake: Vec<(usize, usize)>) {
+        self.death = Some(Death { snake, frame: 0 });
+    }
+
+    pub fn clear(&mut self) {
+        *self = Animations::default();
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.pulses.is_empty() || self.is_dying()
+    }
+
+    pub fn is_dying(&self) -> bool {
+        self.death.as_ref().is_some_and(|death| !death.is_over())
+    }
+
+    //moves every animation one frame on and forgets the finished pulses,
+    //the dead snake stays hidden once it has dissolved
+    pub fn advance(&mut self) {
+        if let Some(death) = self.death.as_mut().filter(|death| !death.is_over()) {
+            death.frame += 1;
+        }
+        self.pulses.iter_mut().for_each(|pulse| pulse.frame += 1);
+        self.pulses.retain(|pulse| pulse.frame < PULSE_FRAMES);
+    }
+
+    pub fn effects(&self) -> Vec<Effect> {
+        self.pulses
+            .iter()
+            .map(|pulse| Effect::Pulse {
+                cell: pulse.cell,
+                radius: pulse.frame / 2 + 1,
+            })
+            .collect()
+    }
+
+    //removes the parts of the dead snake missing from the current frame
+    pub fn apply(&self, grid: &mut [Vec<CellContent>]) {
+        let Some(death) = &self.death else {
+            return;
+        };
+        for &(x, y) in death.hidden() {
+            grid[y][x] = CellContent::Empty;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
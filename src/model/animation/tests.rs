@@ -0,0 +1,76 @@
+use super::{Animations, Effect, FLASH_FRAMES, PULSE_FRAMES};
+use crate::model::{CellContent, Orientation};
+
+fn snake_grid() -> Vec<Vec<CellContent>> {
+    let mut grid = vec![vec![CellContent::Empty; 3]; 3];
+    grid[0][0] = CellContent::Head(Orientation::Up);
+    grid[1][0] = CellContent::Body {
+        towards: Orientation::Up,
+        from: Orientation::Up,
+    };
+    grid[2][0] = CellContent::Tail(Orientation::Up);
+    grid
+}
+
+fn snake_cells(grid: &[Vec<CellContent>]) -> usize {
+    grid.iter()
+        .flatten()
+        .filter(|cell| **cell != CellContent::Empty)
+        .count()
+}
+
+#[test]
+fn test_death_blinks_then_dissolves_from_the_head() {
+    let mut animations = Animations::default();
+    animations.die(vec![(0, 0), (0, 1), (0, 2)]);
+
+    let mut visible = Vec::new();
+    while animations.is_dying() {
+        let mut grid = snake_grid();
+        animations.apply(&mut grid);
+        visible.push(snake_cells(&grid));
+        animations.advance();
+    }
+    assert_eq!(visible, [3, 0, 3, 0, 3, 0, 2, 1, 0]);
+
+    //the snake stays gone for the game over panel
+    let mut grid = snake_grid();
+    animations.apply(&mut grid);
+    assert_eq!(snake_cells(&grid), 0);
+    assert!(!animations.is_running());
+}
+
+#[test]
+fn test_long_snakes_dissolve_in_a_bounded_time() {
+    let mut animations = Animations::default();
+    animations.die((0..100).map(|y| (0, y)).collect());
+    let mut frames = 0;
+    while animations.is_dying() {
+        animations.advance();
+        frames += 1;
+    }
+    assert_eq!(frames, FLASH_FRAMES + 20);
+}
+
+#[test]
+fn test_pulse_grows_and_ends() {
+    let mut animations = Animations::default();
+    animations.pulse((1, 1));
+    let grid = vec![vec![CellContent::Empty; 3]; 3];
+    assert_eq!(
+        animations.effects(),
+        [Effect::Pulse {
+            cell: (1, 1),
+            radius: 1
+        }]
+    );
+    //the ring is clipped to the grid
+    assert_eq!(animations.effects()[0].cells(&grid).len(), 8);
+
+    for _ in 0..PULSE_FRAMES {
+        assert!(animations.is_running());
+        animations.advance();
+    }
+    assert!(!animations.is_running());
+    assert!(animations.effects().is_empty());
+}
@@ -0,0 +1,100 @@
+use crossterm::event::KeyCode;
+
+use super::{
+    title_menu::{MenuEntry, MenuInput},
+    GameState,
+};
+use crate::{
+    keymap::{key_name, KeyAction, Keymap},
+    locale::{self, Messages},
+};
+
+//the screen where every action can be bound to a different key
+#[derive(Clone, Debug, PartialEq)]
+pub struct ControlsMenu {
+    selected: usize,
+    //the next key pressed is bound to the selected action
+    capturing: bool,
+    message: Option<String>,
+    //where to go back to when leaving the screen
+    return_to: GameState,
+}
+
+impl ControlsMenu {
+    pub fn new(return_to: GameState) -> Self {
+        ControlsMenu {
+            selected: 0,
+            capturing: false,
+            message: None,
+            return_to,
+        }
+    }
+
+    pub fn handle_input(&mut self, input: MenuInput) {
+        let len = KeyAction::ALL.len();
+        match input {
+            MenuInput::Up => self.selected = (self.selected + len - 1) % len,
+            MenuInput::Down => self.selected = (self.selected + 1) % len,
+            MenuInput::Select => {
+                self.capturing = true;
+                self.message = None;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.capturing
+    }
+
+    pub fn return_to(&self) -> GameState {
+        self.return_to
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    //binds the captured key to the selected action,
+    //returns whether the keymap was changed
+    pub fn bind(&mut self, key: KeyCode, keymap: &mut Keymap, messages: &Messages) -> bool {
+        self.capturing = false;
+        let action = KeyAction::ALL[self.selected];
+        match keymap.bind(action, key) {
+            Ok(()) => {
+                self.message = None;
+                true
+            }
+            Err(conflict) => {
+                self.message = Some(locale::fill(
+                    messages.already_bound,
+                    &[
+                        ("key", &key_name(conflict.key)),
+                        ("action", &messages.action(conflict.bound_to)),
+                    ],
+                ));
+                false
+            }
+        }
+    }
+
+    pub fn entries(&self, keymap: &Keymap, messages: &Messages) -> Vec<MenuEntry> {
+        KeyAction::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, action)| {
+                let selected = i == self.selected;
+                MenuEntry {
+                    label: messages.action(*action),
+                    value: if selected && self.capturing {
+                        messages.press_a_key.to_string()
+                    } else {
+                        key_name(keymap.key_for(*action))
+                    },
+                    error: None,
+                    selected,
+                }
+            })
+            .collect()
+    }
+}
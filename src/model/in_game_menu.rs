@@ -0,0 +1,65 @@
+use super::title_menu::{MenuEntry, MenuInput};
+use crate::locale::Messages;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InGameMenuItem {
+    Resume,
+    Restart,
+    Settings,
+    QuitToTitle,
+    Exit,
+}
+
+impl InGameMenuItem {
+    pub const ALL: [InGameMenuItem; 5] = [
+        InGameMenuItem::Resume,
+        InGameMenuItem::Restart,
+        InGameMenuItem::Settings,
+        InGameMenuItem::QuitToTitle,
+        InGameMenuItem::Exit,
+    ];
+
+    pub fn label(&self, messages: &Messages) -> &'static str {
+        match self {
+            InGameMenuItem::Resume => messages.resume,
+            InGameMenuItem::Restart => messages.restart,
+            InGameMenuItem::Settings => messages.settings,
+            InGameMenuItem::QuitToTitle => messages.quit_to_title,
+            InGameMenuItem::Exit => messages.exit,
+        }
+    }
+}
+
+//the menu opened with esc while playing, drawn over the frozen board
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InGameMenu {
+    selected: usize,
+}
+
+impl InGameMenu {
+    pub fn handle_input(&mut self, input: MenuInput) {
+        let len = InGameMenuItem::ALL.len();
+        match input {
+            MenuInput::Up => self.selected = (self.selected + len - 1) % len,
+            MenuInput::Down => self.selected = (self.selected + 1) % len,
+            _ => {}
+        }
+    }
+
+    pub fn selected_item(&self) -> InGameMenuItem {
+        InGameMenuItem::ALL[self.selected]
+    }
+
+    pub fn entries(&self, messages: &Messages) -> Vec<MenuEntry> {
+        InGameMenuItem::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, item)| MenuEntry {
+                label: item.label(messages),
+                value: String::new(),
+                error: None,
+                selected: i == self.selected,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,101 @@
+use super::{animation::Effect, title_menu::MenuEntry, CellContent, DeathCause, Hud};
+use crate::{high_scores::HighScore, settings::GameMode, stats::Stats};
+
+//everything a view needs to draw what the player sees,
+//built again by the model whenever any of it changes
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderState {
+    pub screen: Screen,
+    //debug information drawn under the screen, like the latency histograms
+    pub debug: Option<Vec<String>>,
+}
+
+impl RenderState {
+    pub fn new(screen: Screen) -> Self {
+        RenderState {
+            screen,
+            debug: None,
+        }
+    }
+
+    //the board the screen is drawn on, if there is one
+    pub fn board(&self) -> Option<&Board> {
+        match &self.screen {
+            Screen::Playing(board)
+            | Screen::Paused(board)
+            | Screen::Menu { board, .. }
+            | Screen::GameOver { board, .. }
+            | Screen::Victory(board)
+            | Screen::NewHighScore { board, .. }
+            | Screen::ResumeOffer(board) => Some(board),
+            Screen::Title { .. }
+            | Screen::Controls { .. }
+            | Screen::HighScores { .. }
+            | Screen::Stats(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Screen {
+    //controls lists the name of each key with what it does
+    Title {
+        menu: Vec<MenuEntry>,
+        controls: Vec<(String, &'static str)>,
+    },
+    //the key bindings, with what happened to the last key pressed
+    Controls {
+        bindings: Vec<MenuEntry>,
+        message: Option<String>,
+    },
+    //the board alone, also while the dead snake dissolves
+    Playing(Board),
+    Paused(Board),
+    //the in-game menu or the settings, on top of the board
+    Menu {
+        board: Board,
+        title: &'static str,
+        entries: Vec<MenuEntry>,
+    },
+    //the hud of the board has the stats of the game
+    GameOver {
+        board: Board,
+        cause: DeathCause,
+    },
+    //the snake filled the whole board
+    Victory(Board),
+    //the score made the table at rank (from 0), the initials are still being typed
+    NewHighScore {
+        board: Board,
+        rank: usize,
+        initials: String,
+    },
+    //the best scores played with the settings described
+    HighScores {
+        settings: String,
+        scores: Vec<HighScore>,
+    },
+    //the totals of every game played
+    Stats(Stats),
+    //the game saved when quitting last time, waiting to be resumed or thrown away
+    ResumeOffer(Board),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Board {
+    pub grid: Vec<Vec<CellContent>>,
+    pub hud: Hud,
+    pub effects: Vec<Effect>,
+    //whether the snake comes out of the opposite wall instead of crashing
+    pub mode: GameMode,
+}
+
+impl Board {
+    //in cells, (width, height)
+    pub fn size(&self) -> (usize, usize) {
+        (
+            self.grid.first().map_or(0, |row| row.len()),
+            self.grid.len(),
+        )
+    }
+}
@@ -0,0 +1,187 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{neighbour, CellContent};
+use crate::{
+    settings::{Settings, GRID_HEIGHT_RANGE, GRID_WIDTH_RANGE},
+    storage::{self, Dirs},
+};
+
+//raised whenever the saved fields change, saves of another version are not resumed
+pub const SAVE_VERSION: u32 = 1;
+
+const SAVED_GAME_FILE: &str = "saved_game.json";
+
+//a game left halfway through when quitting, offered at the next launch
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub version: u32,
+    pub settings: Settings,
+    pub grid: Vec<Vec<CellContent>>,
+    pub snake_len: usize,
+    pub apples: usize,
+    pub score: usize,
+    pub ticks: u64,
+    //the apples keep coming where they would have without quitting
+    pub rng: ChaCha12Rng,
+}
+
+//read before the rest, which may not even parse in another version
+#[derive(Deserialize)]
+struct Version {
+    version: u32,
+}
+
+impl SavedGame {
+    //the game saved in the state directory, None if there is none
+    pub fn load(dirs: &Dirs) -> io::Result<Option<SavedGame>> {
+        let Some(path) = Self::saved_game_path(dirs) else {
+            return Ok(None);
+        };
+        match Self::load_file(&path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            result => result.map(Some),
+        }
+    }
+
+    pub fn load_file(path: &Path) -> io::Result<SavedGame> {
+        let contents = fs::read_to_string(path)?;
+        Self::from_json(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn from_json(contents: &str) -> Result<SavedGame, String> {
+        let Version { version } = serde_json::from_str(contents).map_err(|e| e.to_string())?;
+        if version != SAVE_VERSION {
+            return Err(format!(
+                "saved with version {version}, this game resumes version {SAVE_VERSION}"
+            ));
+        }
+        let saved_game: SavedGame = serde_json::from_str(contents).map_err(|e| e.to_string())?;
+        saved_game.check()?;
+        Ok(saved_game)
+    }
+
+    pub fn save(&self, dirs: &Dirs) -> io::Result<()> {
+        let path = Self::saved_game_path(dirs)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no state directory"))?;
+        self.save_file(&path)
+    }
+
+    //written whole to a new file first, so a crash can't leave half of it behind
+    pub fn save_file(&self, path: &Path) -> io::Result<()> {
+        let contents = serde_json::to_vec(self).map_err(io::Error::other)?;
+        storage::write_atomic(path, &contents)
+    }
+
+    //forgets the saved game, once it is resumed or thrown away
+    pub fn remove(dirs: &Dirs) -> io::Result<()> {
+        let Some(path) = Self::saved_game_path(dirs) else {
+            return Ok(());
+        };
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    //the model expects a single snake linked from the tail to the head,
+    //snake_len cells long, and an apple somewhere else on the board
+    fn check(&self) -> Result<(), String> {
+        let (width, height) = (self.settings.grid_width, self.settings.grid_height);
+        if !(GRID_WIDTH_RANGE.0..=GRID_WIDTH_RANGE.1).contains(&width)
+            || !(GRID_HEIGHT_RANGE.0..=GRID_HEIGHT_RANGE.1).contains(&height)
+        {
+            return Err(format!(
+                "the board is {width}x{height}, too small or too big to play"
+            ));
+        }
+        if self.grid.len() != height || self.grid.iter().any(|row| row.len() != width) {
+            return Err(format!(
+                "the grid is not {width}x{height} like its settings"
+            ));
+        }
+        //a snake filling the board has already won
+        if !(2..width * height).contains(&self.snake_len) {
+            return Err(format!(
+                "a snake of {} cells cannot be playing",
+                self.snake_len
+            ));
+        }
+
+        let cells_where = |matches: fn(&CellContent) -> bool| -> Vec<(usize, usize)> {
+            self.grid
+                .iter()
+                .enumerate()
+                .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, cell)| (x, y, cell)))
+                .filter(|(_, _, cell)| matches(cell))
+                .map(|(x, y, _)| (x, y))
+                .collect()
+        };
+        let heads = cells_where(|cell| matches!(cell, CellContent::Head(_)));
+        let tails = cells_where(|cell| matches!(cell, CellContent::Tail(_)));
+        let &[(mut x, mut y)] = tails.as_slice() else {
+            return Err(format!(
+                "the snake has {} tails instead of one",
+                tails.len()
+            ));
+        };
+        if heads.len() != 1 {
+            return Err(format!(
+                "the snake has {} heads instead of one",
+                heads.len()
+            ));
+        }
+
+        //every cell must be entered from the way the one before points,
+        //which bounds the walk even if the cells point in a circle
+        let mut length = 1;
+        loop {
+            let towards = match &self.grid[y][x] {
+                CellContent::Tail(towards) | CellContent::Body { towards, .. } => *towards,
+                _ => unreachable!("the walk stops at the head"),
+            };
+            (x, y) = neighbour(self.settings.mode, (width, height), (x, y), towards)
+                .ok_or_else(|| format!("the snake points out of the board at {x},{y}"))?;
+            length += 1;
+            if length > self.snake_len {
+                return Err(format!(
+                    "the snake is longer than its length of {}",
+                    self.snake_len
+                ));
+            }
+            match &self.grid[y][x] {
+                CellContent::Body { from, .. } if *from == towards => {}
+                CellContent::Head(heading) if *heading == towards => break,
+                _ => return Err(format!("the snake is broken at {x},{y}")),
+            }
+        }
+        if length != self.snake_len {
+            return Err(format!(
+                "the snake is {length} cells long instead of {}",
+                self.snake_len
+            ));
+        }
+
+        let bodies = cells_where(|cell| matches!(cell, CellContent::Body { .. }));
+        if bodies.len() + 2 != self.snake_len {
+            return Err("there are pieces of snake outside of the snake".to_string());
+        }
+        let apples = cells_where(|cell| *cell == CellContent::Apple);
+        if apples.len() != 1 {
+            return Err(format!("there are {} apples instead of one", apples.len()));
+        }
+        Ok(())
+    }
+
+    fn saved_game_path(dirs: &Dirs) -> Option<PathBuf> {
+        Some(dirs.state.as_ref()?.join(SAVED_GAME_FILE))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,138 @@
+use std::{env, fs, process};
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+use super::{SavedGame, SAVE_VERSION};
+use crate::{
+    model::{CellContent, Model, Orientation, INITIAL_SNAKE_LEN},
+    settings::{GameMode, Settings},
+};
+
+//a game just started on the default board
+fn saved_game() -> SavedGame {
+    let settings = Settings::default();
+    let mut grid = vec![vec![CellContent::Empty; settings.grid_width]; settings.grid_height];
+    Model::initialize_grid(&mut grid, (settings.grid_width, settings.grid_height));
+    SavedGame {
+        version: SAVE_VERSION,
+        settings,
+        grid,
+        snake_len: INITIAL_SNAKE_LEN,
+        apples: 0,
+        score: 0,
+        ticks: 12,
+        rng: ChaCha12Rng::seed_from_u64(7),
+    }
+}
+
+fn resumed(saved_game: &SavedGame) -> Result<SavedGame, String> {
+    SavedGame::from_json(&serde_json::to_string(saved_game).unwrap())
+}
+
+#[test]
+fn test_save_and_load() {
+    let path = env::temp_dir().join(format!("rusty-rattle-saved-game-{}.json", process::id()));
+    let saved_game = saved_game();
+    saved_game.save_file(&path).unwrap();
+    let loaded = SavedGame::load_file(&path).unwrap();
+    assert_eq!(loaded, saved_game);
+
+    //the apples come in the same places as they would have
+    let (mut before, mut after) = (saved_game.rng, loaded.rng);
+    assert_eq!(
+        rand::Rng::gen::<u64>(&mut before),
+        rand::Rng::gen::<u64>(&mut after)
+    );
+
+    fs::write(&path, "{\"version\": 1, \"grid\": [").unwrap();
+    assert!(SavedGame::load_file(&path).is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_other_versions_are_not_resumed() {
+    let json = serde_json::to_string(&saved_game()).unwrap();
+    let json = json.replace(
+        &format!("\"version\":{SAVE_VERSION}"),
+        &format!("\"version\":{}", SAVE_VERSION + 1),
+    );
+    assert!(SavedGame::from_json(&json).unwrap_err().contains("version"));
+    //whatever else a newer version saves
+    assert!(SavedGame::from_json("{\"version\": 99, \"board\": []}").is_err());
+}
+
+#[test]
+fn test_the_snake_must_be_linked() {
+    let mut saved_game = saved_game();
+    let (x, y) = (
+        saved_game.settings.grid_width / 2,
+        saved_game.settings.grid_height / 2,
+    );
+    saved_game.grid[y + 1][x] = CellContent::Body {
+        towards: Orientation::Up,
+        from: Orientation::Left,
+    };
+    assert_eq!(
+        resumed(&saved_game).unwrap_err(),
+        format!("the snake is broken at {x},{}", y + 1)
+    );
+
+    let mut saved_game = self::saved_game();
+    saved_game.grid[y][x] = CellContent::Empty;
+    assert!(resumed(&saved_game).unwrap_err().contains("heads"));
+}
+
+#[test]
+fn test_the_snake_must_be_as_long_as_saved() {
+    let mut saved_game = saved_game();
+    saved_game.snake_len = 5;
+    assert_eq!(
+        resumed(&saved_game).unwrap_err(),
+        "the snake is 4 cells long instead of 5"
+    );
+    saved_game.snake_len = 3;
+    assert_eq!(
+        resumed(&saved_game).unwrap_err(),
+        "the snake is longer than its length of 3"
+    );
+
+    //a piece of body that is not part of the snake
+    let mut saved_game = self::saved_game();
+    saved_game.grid[0][0] = CellContent::Body {
+        towards: Orientation::Up,
+        from: Orientation::Up,
+    };
+    assert!(resumed(&saved_game).is_err());
+}
+
+#[test]
+fn test_the_snake_goes_across_the_edges_in_wrap_mode() {
+    let settings = Settings {
+        grid_height: 8,
+        grid_width: 8,
+        mode: GameMode::Wrap,
+        ..Settings::default()
+    };
+    let mut grid = vec![vec![CellContent::Empty; 8]; 8];
+    grid[1][0] = CellContent::Tail(Orientation::Up);
+    grid[0][0] = CellContent::Body {
+        towards: Orientation::Up,
+        from: Orientation::Up,
+    };
+    grid[7][0] = CellContent::Head(Orientation::Up);
+    grid[4][4] = CellContent::Apple;
+    let mut saved_game = SavedGame {
+        settings,
+        grid,
+        snake_len: 3,
+        ..saved_game()
+    };
+    assert_eq!(resumed(&saved_game), Ok(saved_game.clone()));
+
+    saved_game.settings.mode = GameMode::Classic;
+    assert_eq!(
+        resumed(&saved_game).unwrap_err(),
+        "the snake points out of the board at 0,0"
+    );
+}
@@ -0,0 +1,203 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::{
+    locale::{self, Messages},
+    settings::{GameMode, Settings, GRID_HEIGHT_RANGE, GRID_WIDTH_RANGE, UPDATE_INTERVAL_MS_RANGE},
+    views::Theme,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MenuInput {
+    Up,
+    Down,
+    Left,
+    Right,
+    Digit(u32),
+    Backspace,
+    Select,
+    Back,
+}
+
+//a single line of a menu, as the views should draw it
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MenuEntry {
+    pub label: &'static str,
+    pub value: String,
+    pub error: Option<String>,
+    pub selected: bool,
+}
+
+//a number typed digit by digit, validated against its range after every key
+#[derive(Clone, Debug, PartialEq)]
+struct NumberField {
+    text: String,
+    range: (usize, usize),
+    step: usize,
+}
+
+impl NumberField {
+    fn new(value: usize, range: (usize, usize), step: usize) -> Self {
+        NumberField {
+            text: value.to_string(),
+            range,
+            step,
+        }
+    }
+
+    fn value(&self) -> Option<usize> {
+        let (min, max) = self.range;
+        self.text
+            .parse()
+            .ok()
+            .filter(|value| (min..=max).contains(value))
+    }
+
+    fn handle_input(&mut self, input: MenuInput) {
+        let (min, max) = self.range;
+        match input {
+            MenuInput::Digit(digit) => {
+                if self.text.len() < max.to_string().len() {
+                    self.text.push(char::from_digit(digit, 10).unwrap_or('0'));
+                }
+            }
+            MenuInput::Backspace => {
+                self.text.pop();
+            }
+            MenuInput::Left => {
+                let value = self.value().unwrap_or(min);
+                self.text = value.saturating_sub(self.step).max(min).to_string();
+            }
+            MenuInput::Right => {
+                let value = self.value().unwrap_or(min);
+                self.text = (value + self.step).min(max).to_string();
+            }
+            MenuInput::Up | MenuInput::Down | MenuInput::Select | MenuInput::Back => {}
+        }
+    }
+
+    fn entry(&self, label: &'static str, selected: bool, messages: &Messages) -> MenuEntry {
+        let (min, max) = self.range;
+        MenuEntry {
+            label,
+            value: self.text.clone(),
+            error: self
+                .value()
+                .is_none()
+                .then(|| locale::fill(messages.out_of_range, &[("min", &min), ("max", &max)])),
+            selected,
+        }
+    }
+}
+
+//the settings the player picks on the title screen before starting a game
+#[derive(Clone, Debug, PartialEq)]
+pub struct TitleMenu {
+    selected: usize,
+    grid_height: NumberField,
+    grid_width: NumberField,
+    update_interval: NumberField,
+    mode: GameMode,
+    theme: Theme,
+    themes: Vec<Theme>,
+}
+
+impl TitleMenu {
+    const ENTRIES: usize = 8;
+
+    pub fn new(settings: &Settings) -> Self {
+        TitleMenu {
+            selected: 0,
+            grid_height: NumberField::new(settings.grid_height, GRID_HEIGHT_RANGE, 1),
+            grid_width: NumberField::new(settings.grid_width, GRID_WIDTH_RANGE, 1),
+            update_interval: NumberField::new(
+                settings.update_interval.as_millis() as usize,
+                UPDATE_INTERVAL_MS_RANGE,
+                10,
+            ),
+            mode: settings.mode,
+            theme: settings.theme.clone(),
+            themes: Theme::available(),
+        }
+    }
+
+    pub fn handle_input(&mut self, input: MenuInput) {
+        match input {
+            MenuInput::Up => self.selected = (self.selected + Self::ENTRIES - 1) % Self::ENTRIES,
+            MenuInput::Down => self.selected = (self.selected + 1) % Self::ENTRIES,
+            _ => match self.selected {
+                0 => self.grid_height.handle_input(input),
+                1 => self.grid_width.handle_input(input),
+                2 => self.update_interval.handle_input(input),
+                3 => self.mode = cycle(&GameMode::ALL, &self.mode, input),
+                4 => self.theme = cycle(&self.themes, &self.theme, input),
+                //the controls, the high scores and the stats open their own screens
+                5..=7 => {}
+                _ => unreachable!("the menu has {} entries", Self::ENTRIES),
+            },
+        }
+    }
+
+    pub fn entries(&self, messages: &Messages) -> Vec<MenuEntry> {
+        let choice = |label, value: &str, index| MenuEntry {
+            label,
+            value: value.to_string(),
+            error: None,
+            selected: self.selected == index,
+        };
+        vec![
+            self.grid_height
+                .entry(messages.grid_height, self.selected == 0, messages),
+            self.grid_width
+                .entry(messages.grid_width, self.selected == 1, messages),
+            self.update_interval
+                .entry(messages.step_interval, self.selected == 2, messages),
+            choice(messages.mode, messages.game_mode(self.mode), 3),
+            choice(messages.theme, self.theme.name(), 4),
+            choice(messages.controls, "", 5),
+            choice(messages.high_scores, "", 6),
+            choice(messages.lifetime_stats, "", 7),
+        ]
+    }
+
+    pub fn controls_selected(&self) -> bool {
+        self.selected == 5
+    }
+
+    pub fn high_scores_selected(&self) -> bool {
+        self.selected == 6
+    }
+
+    pub fn stats_selected(&self) -> bool {
+        self.selected == 7
+    }
+
+    //the chosen settings, None while any of them is invalid,
+    //which the entries already show as errors
+    pub fn settings(&self) -> Option<Settings> {
+        let grid_height = self.grid_height.value()?;
+        let grid_width = self.grid_width.value()?;
+        let update_interval = self.update_interval.value()?;
+        Some(Settings {
+            grid_height,
+            grid_width,
+            update_interval: Duration::from_millis(update_interval as u64),
+            mode: self.mode,
+            theme: self.theme.clone(),
+        })
+    }
+}
+
+//left and right go through the choices, wrapping around
+fn cycle<T: Clone + PartialEq>(all: &[T], current: &T, input: MenuInput) -> T {
+    let index = all.iter().position(|item| item == current).unwrap_or(0);
+    match input {
+        MenuInput::Left => all[(index + all.len() - 1) % all.len()].clone(),
+        MenuInput::Right => all[(index + 1) % all.len()].clone(),
+        _ => current.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
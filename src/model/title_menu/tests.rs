@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use super::{MenuInput, TitleMenu};
+use crate::{
+    locale::{Locale, Messages},
+    settings::{GameMode, Settings},
+    views::Theme,
+};
+
+const MESSAGES: &Messages = Locale::English.messages();
+
+fn default_settings() -> Settings {
+    Settings {
+        grid_height: 20,
+        grid_width: 30,
+        update_interval: Duration::from_millis(200),
+        mode: GameMode::Classic,
+        theme: Theme::default(),
+    }
+}
+
+#[test]
+fn test_menu_keeps_initial_settings() {
+    let menu = TitleMenu::new(&default_settings());
+    assert_eq!(menu.settings(), Some(default_settings()));
+    assert!(menu.entries(MESSAGES)[0].selected);
+}
+
+#[test]
+fn test_menu_live_validation() {
+    let mut menu = TitleMenu::new(&default_settings());
+
+    //erase the height and type an out of range value
+    menu.handle_input(MenuInput::Backspace);
+    menu.handle_input(MenuInput::Backspace);
+    assert!(menu.entries(MESSAGES)[0].error.is_some());
+    menu.handle_input(MenuInput::Digit(5));
+    assert_eq!(menu.entries(MESSAGES)[0].value, "5");
+    assert_eq!(
+        menu.entries(MESSAGES)[0].error.as_deref(),
+        Some("must be between 8 and 100")
+    );
+    assert_eq!(
+        menu.entries(Locale::Italian.messages())[0].error.as_deref(),
+        Some("deve essere tra 8 e 100")
+    );
+    assert!(menu.settings().is_none());
+
+    menu.handle_input(MenuInput::Digit(0));
+    assert_eq!(menu.entries(MESSAGES)[0].error, None);
+    assert_eq!(menu.settings().unwrap().grid_height, 50);
+
+    //numbers longer than the maximum cannot be typed
+    menu.handle_input(MenuInput::Digit(0));
+    menu.handle_input(MenuInput::Digit(0));
+    assert_eq!(menu.entries(MESSAGES)[0].value, "500");
+    assert!(menu.settings().is_none());
+}
+
+#[test]
+fn test_menu_navigation() {
+    let mut menu = TitleMenu::new(&default_settings());
+
+    //going up from the first entry wraps around to the stats
+    menu.handle_input(MenuInput::Up);
+    assert!(menu.stats_selected());
+    menu.handle_input(MenuInput::Up);
+    assert!(menu.high_scores_selected());
+    menu.handle_input(MenuInput::Up);
+    assert!(menu.controls_selected());
+    menu.handle_input(MenuInput::Up);
+    assert!(menu.entries(MESSAGES)[4].selected);
+    menu.handle_input(MenuInput::Right);
+    assert_eq!(menu.settings().unwrap().theme.name(), "monochrome");
+
+    menu.handle_input(MenuInput::Up);
+    menu.handle_input(MenuInput::Left);
+    assert_eq!(menu.settings().unwrap().mode, GameMode::Wrap);
+
+    //the step interval is changed in steps of 10ms and clamped to its range
+    menu.handle_input(MenuInput::Up);
+    menu.handle_input(MenuInput::Right);
+    assert_eq!(
+        menu.settings().unwrap().update_interval,
+        Duration::from_millis(210)
+    );
+    for _ in 0..30 {
+        menu.handle_input(MenuInput::Left);
+    }
+    assert_eq!(
+        menu.settings().unwrap().update_interval,
+        Duration::from_millis(20)
+    );
+}
@@ -0,0 +1,222 @@
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    str::FromStr,
+    sync::mpsc::{self, Sender},
+    thread,
+    time::Instant,
+};
+
+#[cfg(unix)]
+use std::{
+    fs,
+    os::unix::{
+        fs::FileTypeExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{controller::Action, event_processes::Event, model::Orientation};
+
+//where the remote control listener accepts connections,
+//written as "tcp:127.0.0.1:7777" or "unix:/tmp/rusty-rattle.sock"
+#[derive(Clone, Debug, PartialEq)]
+pub enum RemoteAddr {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for RemoteAddr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("tcp", addr)) => addr
+                .parse()
+                .map(RemoteAddr::Tcp)
+                .map_err(|e| format!("invalid tcp address {addr:?}: {e}")),
+            #[cfg(unix)]
+            Some(("unix", path)) if !path.is_empty() => Ok(RemoteAddr::Unix(path.into())),
+            _ => Err(format!(
+                "invalid remote address {s:?}, expected tcp:<host>:<port> or unix:<path>"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RemoteAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteAddr::Tcp(addr) => write!(f, "tcp:{addr}"),
+            #[cfg(unix)]
+            RemoteAddr::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+//one line of the protocol, e.g. {"cmd": "turn", "direction": "left"}
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Command {
+    Turn { direction: Orientation },
+    Start,
+    Pause,
+    Quit,
+}
+
+impl Command {
+    fn into_event(self) -> Event {
+        let now = Instant::now();
+        match self {
+            Command::Turn { direction } => Event::Action(Action::Turn(direction), now),
+            Command::Start => Event::Action(Action::Start, now),
+            Command::Pause => Event::Action(Action::Pause, now),
+            Command::Quit => Event::Quit,
+        }
+    }
+}
+
+//the remote control while the game runs, a unix socket is removed
+//when it is dropped so the next run finds the path free
+pub struct Listening {
+    #[cfg(unix)]
+    socket: Option<PathBuf>,
+}
+
+impl Drop for Listening {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(path) = &self.socket {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+//binds the address right away, so a taken one is reported when the game starts,
+//then accepts remote clients on a thread of its own until the game quits
+pub fn listen(addr: &RemoteAddr, sender: Sender<Event>) -> io::Result<Listening> {
+    let report = |addr: &RemoteAddr, result: io::Result<()>| {
+        if let Err(e) = result {
+            eprintln!("remote control listener on {addr} stopped: {e}\r");
+        }
+    };
+    match addr {
+        RemoteAddr::Tcp(socket_addr) => {
+            let listener = TcpListener::bind(socket_addr)?;
+            let addr = addr.clone();
+            thread::spawn(move || {
+                report(
+                    &addr,
+                    accept_loop(listener.incoming(), TcpStream::try_clone, sender),
+                )
+            });
+            Ok(Listening {
+                #[cfg(unix)]
+                socket: None,
+            })
+        }
+        #[cfg(unix)]
+        RemoteAddr::Unix(path) => {
+            remove_stale_socket(path)?;
+            let listener = UnixListener::bind(path)?;
+            let addr = addr.clone();
+            thread::spawn(move || {
+                report(
+                    &addr,
+                    accept_loop(listener.incoming(), UnixStream::try_clone, sender),
+                )
+            });
+            Ok(Listening {
+                socket: Some(path.clone()),
+            })
+        }
+    }
+}
+
+//each client is served on its own thread, with a clone of its stream to write to
+fn accept_loop<S: Read + Write + Send + 'static>(
+    incoming: impl Iterator<Item = io::Result<S>>,
+    try_clone: fn(&S) -> io::Result<S>,
+    sender: Sender<Event>,
+) -> io::Result<()> {
+    for stream in incoming {
+        let stream = stream?;
+        let writer = try_clone(&stream)?;
+        let sender = sender.clone();
+        thread::spawn(move || serve_connection(stream, writer, sender));
+    }
+    Ok(())
+}
+
+//a socket left behind by a previous run would make bind fail, but one another
+//running game still answers on is not ours to take, and neither is any other file
+#[cfg(unix)]
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => match UnixStream::connect(path) {
+            Ok(_) => Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("{} is in use by another running game", path.display()),
+            )),
+            Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => fs::remove_file(path),
+            Err(e) => Err(e),
+        },
+        Ok(_) => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} exists and is not a socket", path.display()),
+        )),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+//reads commands from the client and writes back the snapshots sent after each tick
+pub fn serve_connection(
+    reader: impl Read,
+    mut writer: impl Write + Send + 'static,
+    sender: Sender<Event>,
+) {
+    let (line_sender, line_receiver) = mpsc::channel::<String>();
+    if sender.send(Event::Subscribe(line_sender.clone())).is_err() {
+        return;
+    }
+
+    thread::spawn(move || {
+        for line in line_receiver {
+            if writeln!(writer, "{line}")
+                .and_then(|_| writer.flush())
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    for line in BufReader::new(reader).lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Command>(&line) {
+            Ok(command) => {
+                if sender.send(command.into_event()).is_err() {
+                    break;
+                }
+            }
+            Err(e) => {
+                let error = serde_json::json!({ "error": e.to_string() });
+                if line_sender.send(error.to_string()).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,117 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+    thread,
+};
+
+#[cfg(unix)]
+use super::{listen, remove_stale_socket};
+use super::{serve_connection, Command, RemoteAddr};
+use crate::{controller::Action, event_processes::Event, model::Orientation};
+
+#[test]
+fn test_parse_commands() {
+    assert_eq!(
+        serde_json::from_str::<Command>(r#"{"cmd": "turn", "direction": "left"}"#).unwrap(),
+        Command::Turn {
+            direction: Orientation::Left
+        }
+    );
+    assert_eq!(
+        serde_json::from_str::<Command>(r#"{"cmd": "start"}"#).unwrap(),
+        Command::Start
+    );
+    assert!(serde_json::from_str::<Command>(r#"{"cmd": "jump"}"#).is_err());
+    assert!(serde_json::from_str::<Command>(r#"{"cmd": "turn"}"#).is_err());
+}
+
+#[test]
+fn test_parse_remote_addr() {
+    assert_eq!(
+        "tcp:127.0.0.1:7777".parse::<RemoteAddr>(),
+        Ok(RemoteAddr::Tcp("127.0.0.1:7777".parse().unwrap()))
+    );
+    #[cfg(unix)]
+    assert_eq!(
+        "unix:/tmp/rattle.sock".parse::<RemoteAddr>(),
+        Ok(RemoteAddr::Unix("/tmp/rattle.sock".into()))
+    );
+    assert!("127.0.0.1:7777".parse::<RemoteAddr>().is_err());
+    assert!("tcp:localhost".parse::<RemoteAddr>().is_err());
+}
+
+#[test]
+fn test_serve_connection() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let writer = stream.try_clone().unwrap();
+        serve_connection(stream, writer, sender);
+    });
+
+    let mut client = TcpStream::connect(addr).unwrap();
+    let mut client_reader = BufReader::new(client.try_clone().unwrap());
+
+    //the connection subscribes to the snapshots first
+    let Event::Subscribe(snapshots) = receiver.recv().unwrap() else {
+        panic!("expected a subscription");
+    };
+
+    writeln!(client, r#"{{"cmd": "turn", "direction": "down"}}"#).unwrap();
+    match receiver.recv().unwrap() {
+        Event::Action(Action::Turn(Orientation::Down), _) => {}
+        _ => panic!("expected a turn action"),
+    }
+
+    //invalid commands are answered with an error line
+    writeln!(client, "not json").unwrap();
+    let mut line = String::new();
+    client_reader.read_line(&mut line).unwrap();
+    assert!(line.starts_with(r#"{"error":"#));
+
+    snapshots
+        .send(r#"{"state":"playing"}"#.to_string())
+        .unwrap();
+    line.clear();
+    client_reader.read_line(&mut line).unwrap();
+    assert_eq!(line, "{\"state\":\"playing\"}\n");
+
+    writeln!(client, r#"{{"cmd": "quit"}}"#).unwrap();
+    assert!(matches!(receiver.recv().unwrap(), Event::Quit));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_only_stale_sockets_are_removed() {
+    use std::{env, fs, os::unix::net::UnixListener, process};
+
+    let path = env::temp_dir().join(format!("rusty-rattle-remote-{}.sock", process::id()));
+    //a typo in the address must not delete the file it names
+    fs::write(&path, "notes").unwrap();
+    let error = remove_stale_socket(&path).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::AlreadyExists);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "notes");
+    fs::remove_file(&path).unwrap();
+
+    //nothing there yet, or the socket of a previous run
+    remove_stale_socket(&path).unwrap();
+    drop(UnixListener::bind(&path).unwrap());
+    remove_stale_socket(&path).unwrap();
+    assert!(!path.exists());
+
+    //another game still listening keeps its socket
+    let (sender, _receiver) = mpsc::channel();
+    let addr = RemoteAddr::Unix(path.clone());
+    let listening = listen(&addr, sender.clone()).unwrap();
+    let error = listen(&addr, sender).err().unwrap();
+    assert_eq!(error.kind(), std::io::ErrorKind::AddrInUse);
+    assert!(path.exists());
+
+    //and removes it when it quits
+    drop(listening);
+    assert!(!path.exists());
+}
@@ -0,0 +1,110 @@
+use std::{fs, io, path::PathBuf, str::FromStr, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    storage::{self, Dirs},
+    views::Theme,
+};
+
+pub const GRID_HEIGHT_RANGE: (usize, usize) = (8, 100);
+pub const GRID_WIDTH_RANGE: (usize, usize) = (8, 200);
+pub const UPDATE_INTERVAL_MS_RANGE: (usize, usize) = (20, 2000);
+//a shorthand for the speed, from 300ms per step at level 1 to 30ms at the last one
+pub const LEVEL_RANGE: (usize, usize) = (1, 10);
+
+const LAST_SETTINGS_FILE: &str = "last_settings.json";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    //hitting a wall ends the game
+    #[default]
+    Classic,
+    //the snake comes out of the opposite wall
+    Wrap,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 2] = [GameMode::Classic, GameMode::Wrap];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "classic",
+            GameMode::Wrap => "wrap",
+        }
+    }
+}
+
+impl FromStr for GameMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GameMode::ALL
+            .into_iter()
+            .find(|mode| mode.name() == s.to_lowercase())
+            .ok_or_else(|| format!("unknown mode {s:?}, expected classic or wrap"))
+    }
+}
+
+//the step interval of a level in LEVEL_RANGE
+pub fn level_interval(level: usize) -> Duration {
+    let level = level.clamp(LEVEL_RANGE.0, LEVEL_RANGE.1) as u64;
+    Duration::from_millis(300 - (level - 1) * 30)
+}
+
+//what a single game is played with, chosen in the title screen menu
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    pub grid_height: usize,
+    pub grid_width: usize,
+    #[serde(rename = "update_interval_ms", with = "millis")]
+    pub update_interval: Duration,
+    pub mode: GameMode,
+    pub theme: Theme,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            grid_height: 20,
+            grid_width: 30,
+            update_interval: Duration::from_millis(200),
+            mode: GameMode::default(),
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl Settings {
+    //the settings chosen the last time a game was started, if any
+    pub fn load_last(dirs: &Dirs) -> Option<Settings> {
+        let contents = fs::read_to_string(Self::last_settings_path(dirs)?).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    pub fn save_last(&self, dirs: &Dirs) -> io::Result<()> {
+        let path = Self::last_settings_path(dirs)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no state directory"))?;
+        let contents = serde_json::to_vec_pretty(self).map_err(io::Error::other)?;
+        storage::write_atomic(&path, &contents)
+    }
+
+    fn last_settings_path(dirs: &Dirs) -> Option<PathBuf> {
+        Some(dirs.state.as_ref()?.join(LAST_SETTINGS_FILE))
+    }
+}
+
+mod millis {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
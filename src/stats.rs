@@ -0,0 +1,155 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    locale::{self, Messages},
+    model::DeathCause,
+    storage::{self, Dirs},
+};
+
+const STATS_FILE: &str = "stats.json";
+
+//the totals of every game played on this account, kept between runs
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub games_played: u64,
+    pub apples: u64,
+    pub longest_snake: usize,
+    pub play_time_ms: u64,
+    //the games that ended with the snake running into something
+    pub deaths: BTreeMap<DeathCause, u64>,
+    //the games won by filling the whole board
+    pub wins: u64,
+    //the games restarted or left for the title screen before they ended
+    pub abandoned: u64,
+}
+
+impl Stats {
+    //the stats saved in the data directory, none if there are none yet,
+    //an error when the file is there but can't be read, so it isn't saved over
+    pub fn load(dirs: &Dirs) -> io::Result<Stats> {
+        match Self::stats_path(dirs) {
+            Some(path) => Self::load_or_set_aside(&path),
+            None => Ok(Stats::default()),
+        }
+    }
+
+    //a file that can't be parsed is moved aside and the totals start over
+    pub fn load_or_set_aside(path: &Path) -> io::Result<Stats> {
+        storage::load_or_set_aside(path, Self::load_file).map(Option::unwrap_or_default)
+    }
+
+    pub fn load_file(path: &Path) -> io::Result<Stats> {
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, dirs: &Dirs) -> io::Result<()> {
+        let path = Self::stats_path(dirs)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        self.save_file(&path)
+    }
+
+    pub fn save_file(&self, path: &Path) -> io::Result<()> {
+        let contents = serde_json::to_vec_pretty(self).map_err(io::Error::other)?;
+        storage::write_atomic(path, &contents)
+    }
+
+    //a game that ended, by a death or by filling the board when cause is None
+    pub fn record_game(
+        &mut self,
+        apples: usize,
+        length: usize,
+        play_time: Duration,
+        cause: Option<DeathCause>,
+    ) {
+        self.add_game(apples, length, play_time);
+        match cause {
+            Some(cause) => *self.deaths.entry(cause).or_default() += 1,
+            None => self.wins += 1,
+        }
+    }
+
+    //a game left before it ended, its apples and time count all the same
+    pub fn record_abandoned_game(&mut self, apples: usize, length: usize, play_time: Duration) {
+        self.add_game(apples, length, play_time);
+        self.abandoned += 1;
+    }
+
+    fn add_game(&mut self, apples: usize, length: usize, play_time: Duration) {
+        self.games_played += 1;
+        self.apples += apples as u64;
+        self.longest_snake = self.longest_snake.max(length);
+        self.play_time_ms += play_time.as_millis() as u64;
+    }
+
+    pub fn play_time(&self) -> Duration {
+        Duration::from_millis(self.play_time_ms)
+    }
+
+    pub fn average_game(&self) -> Duration {
+        self.play_time()
+            .checked_div(self.games_played as u32)
+            .unwrap_or_default()
+    }
+
+    pub fn deaths_by(&self, cause: DeathCause) -> u64 {
+        self.deaths.get(&cause).copied().unwrap_or_default()
+    }
+
+    //what the stats screen shows, one line each
+    pub fn lines(&self, messages: &Messages) -> Vec<String> {
+        vec![
+            locale::fill(messages.games_played, &[("games", &self.games_played)]),
+            locale::fill(messages.apples_eaten, &[("apples", &self.apples)]),
+            locale::fill(messages.longest_snake, &[("length", &self.longest_snake)]),
+            locale::fill(
+                messages.time_played,
+                &[("time", &format_duration(self.play_time()))],
+            ),
+            locale::fill(
+                messages.average_game,
+                &[("time", &format_duration(self.average_game()))],
+            ),
+            String::new(),
+            messages.game_endings.to_string(),
+            format!(
+                "  {}: {}",
+                messages.hit_wall,
+                self.deaths_by(DeathCause::Wall)
+            ),
+            format!(
+                "  {}: {}",
+                messages.hit_body,
+                self.deaths_by(DeathCause::Body)
+            ),
+            format!("  {}: {}", messages.filled_board, self.wins),
+            format!("  {}: {}", messages.left_unfinished, self.abandoned),
+        ]
+    }
+
+    fn stats_path(dirs: &Dirs) -> Option<PathBuf> {
+        Some(dirs.data.as_ref()?.join(STATS_FILE))
+    }
+}
+
+//hours:minutes:seconds
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests;
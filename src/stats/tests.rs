@@ -0,0 +1,83 @@
+use std::{env, fs, process, time::Duration};
+
+use super::{format_duration, Stats};
+use crate::{locale::Locale, model::DeathCause};
+
+fn played() -> Stats {
+    let mut stats = Stats::default();
+    stats.record_game(3, 7, Duration::from_secs(40), Some(DeathCause::Wall));
+    stats.record_game(10, 14, Duration::from_secs(90), Some(DeathCause::Body));
+    stats.record_game(0, 4, Duration::from_secs(5), Some(DeathCause::Wall));
+    stats.record_game(60, 64, Duration::from_secs(3400), None);
+    stats.record_abandoned_game(2, 6, Duration::from_secs(65));
+    stats
+}
+
+#[test]
+fn test_games_add_up() {
+    let stats = played();
+    assert_eq!(stats.games_played, 5);
+    assert_eq!(stats.apples, 75);
+    assert_eq!(stats.longest_snake, 64);
+    assert_eq!(stats.play_time(), Duration::from_secs(3600));
+    assert_eq!(stats.average_game(), Duration::from_secs(720));
+    assert_eq!(stats.deaths_by(DeathCause::Wall), 2);
+    assert_eq!(stats.deaths_by(DeathCause::Body), 1);
+    assert_eq!(stats.wins, 1);
+    //the games left before the end are counted apart from the ones that ended
+    assert_eq!(stats.abandoned, 1);
+
+    //nothing to average before the first game
+    assert_eq!(Stats::default().average_game(), Duration::ZERO);
+}
+
+#[test]
+fn test_stats_lines() {
+    assert_eq!(
+        played().lines(Locale::English.messages()),
+        [
+            "games played: 5",
+            "apples eaten: 75",
+            "longest snake: 64",
+            "time played: 1:00:00",
+            "average game: 0:12:00",
+            "",
+            "how the games ended:",
+            "  you hit the wall: 2",
+            "  you ran into yourself: 1",
+            "  the snake filled the whole board: 1",
+            "  you left before the end: 1",
+        ]
+    );
+    assert_eq!(format_duration(Duration::from_secs(37_262)), "10:21:02");
+}
+
+#[test]
+fn test_save_and_load() {
+    let path = env::temp_dir().join(format!("rusty-rattle-stats-{}.json", process::id()));
+    let stats = played();
+    stats.save_file(&path).unwrap();
+    assert_eq!(Stats::load_file(&path).unwrap(), stats);
+    assert!(fs::read_to_string(&path).unwrap().contains("\"wall\": 2"));
+
+    //totals missing from an older file start from zero
+    fs::write(&path, "{\"games_played\": 2, \"apples\": 5}").unwrap();
+    let stats = Stats::load_file(&path).unwrap();
+    assert_eq!((stats.games_played, stats.apples, stats.wins), (2, 5, 0));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_unreadable_stats_are_set_aside() {
+    let path = env::temp_dir().join(format!("rusty-rattle-bad-stats-{}.json", process::id()));
+    let bad_path = path.with_extension("json.bad");
+    fs::write(&path, "{\"games_played\": ").unwrap();
+    assert_eq!(Stats::load_or_set_aside(&path).unwrap(), Stats::default());
+    //the old totals are kept for the player, not saved over by the next game
+    assert!(!path.exists());
+    assert_eq!(
+        fs::read_to_string(&bad_path).unwrap(),
+        "{\"games_played\": "
+    );
+    fs::remove_file(&bad_path).unwrap();
+}
@@ -0,0 +1,124 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+const APP_DIR: &str = "rusty-rattle";
+
+//where the user's own configuration lives
+//($XDG_CONFIG_HOME/rusty-rattle, falling back to ~/.config/rusty-rattle)
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", &[".config"])
+}
+
+//where the game remembers things between runs, like the last chosen settings
+//($XDG_STATE_HOME/rusty-rattle, falling back to ~/.local/state/rusty-rattle)
+pub fn state_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_STATE_HOME", &[".local", "state"])
+}
+
+//where the game keeps what it collects over time, like the lifetime stats
+//($XDG_DATA_HOME/rusty-rattle, falling back to ~/.local/share/rusty-rattle)
+pub fn data_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", &[".local", "share"])
+}
+
+//the directories the game keeps its files in, the user's ones unless told
+//otherwise, which keeps the tests away from the files of the player
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dirs {
+    pub config: Option<PathBuf>,
+    pub state: Option<PathBuf>,
+    pub data: Option<PathBuf>,
+}
+
+impl Dirs {
+    pub fn user() -> Dirs {
+        Dirs {
+            config: config_dir(),
+            state: state_dir(),
+            data: data_dir(),
+        }
+    }
+
+    //all of them in their own subdirectory of root
+    pub fn under(root: &Path) -> Dirs {
+        Dirs {
+            config: Some(root.join("config")),
+            state: Some(root.join("state")),
+            data: Some(root.join("data")),
+        }
+    }
+}
+
+fn xdg_dir(xdg_var: &str, home_fallback: &[&str]) -> Option<PathBuf> {
+    if let Some(dir) = env::var_os(xdg_var).filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir).join(APP_DIR));
+    }
+    if cfg!(target_os = "windows") {
+        if let Some(dir) = env::var_os("APPDATA") {
+            return Some(PathBuf::from(dir).join(APP_DIR));
+        }
+    }
+    let mut dir = PathBuf::from(env::var_os("HOME")?);
+    dir.extend(home_fallback);
+    Some(dir.join(APP_DIR))
+}
+
+//writes to a temporary file next to the destination and then renames it,
+//so a crash halfway through never leaves a truncated file behind
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    //a name of its own, so another instance of the game saving the same file
+    //at the same time never writes into it
+    static WRITES: AtomicU64 = AtomicU64::new(0);
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(format!(
+        ".{}.{}.tmp",
+        process::id(),
+        WRITES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let written = fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&tmp_path, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    written
+}
+
+//reads a file the game keeps adding to, None when there is none yet.
+//one that can't be parsed is renamed to <name>.bad and reported, so the next
+//save starts over next to it instead of replacing everything it held
+pub fn load_or_set_aside<T>(
+    path: &Path,
+    load: impl FnOnce(&Path) -> io::Result<T>,
+) -> io::Result<Option<T>> {
+    match load(path) {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+            let mut bad_path = path.as_os_str().to_owned();
+            bad_path.push(".bad");
+            let bad_path = PathBuf::from(bad_path);
+            fs::rename(path, &bad_path)?;
+            eprintln!(
+                "{} cannot be read and was moved to {}: {e}\r",
+                path.display(),
+                bad_path.display()
+            );
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
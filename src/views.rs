@@ -1,18 +1,111 @@
-use tui_view::TuiView;
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+    sync::mpsc::Sender,
+};
 
-use crate::{model::CellContent, Config, ViewType};
+use export::ExportView;
+use image_view::{ImageOutput, ImageView, DEFAULT_CELL_SIZE};
+use multi_view::MultiView;
+use narrated_view::NarratedView;
+use recorder::Recorder;
+use theme::ColorSupport;
+use tui_view::{ScreenSize, TuiView};
+use web_view::WebView;
 
+use crate::{
+    event_processes::Event, locale::Locale, model::render_state::RenderState, Config, ViewType,
+};
+
+pub mod export;
+pub mod glyphs;
+pub mod image_view;
+pub mod multi_view;
+pub mod narrated_view;
+pub mod recorder;
+pub mod theme;
+pub mod tiles;
 pub mod tui_view;
+pub mod web_view;
+
+pub use theme::Theme;
 
 pub trait View: Send {
-    fn draw_title_screen(&self);
-    fn draw_frame(&self, grid: Vec<Vec<CellContent>>);
-    fn draw_game_over(&self, score: usize);
+    fn set_theme(&self, theme: &Theme);
+    //the language of the text the view draws itself, the menus come already translated
+    fn set_locale(&self, locale: Locale);
+    //draws the whole screen the state describes, every screen and overlay
+    //of the game is one of its variants
+    fn render(&self, state: &RenderState);
 }
 
-pub fn new_view(config: Config) -> Box<dyn View> {
-    match config.view_type() {
-        ViewType::GUI => todo!(),
-        ViewType::TUI => Box::new(TuiView::new()),
+//how many calls a mirrored view can fall behind before it misses some
+const MIRROR_QUEUE_CAPACITY: usize = 64;
+//the export view falls behind while it encodes a game, and the next one
+//should not miss its first frames meanwhile
+const EXPORT_QUEUE_CAPACITY: usize = 1024;
+
+//the mirrored views and the export draw on their own threads, next to
+//the main one, and the recorder gets what the terminal view writes
+pub fn new_view(
+    config: &Config,
+    recorder: &Recorder,
+    events: Sender<Event>,
+) -> io::Result<Box<dyn View>> {
+    let view = view_of_type(*config.view_type(), config, recorder, events.clone())?;
+    if config.mirror_views().is_empty() && config.export().is_none() {
+        return Ok(view);
+    }
+    let mut multi_view = MultiView::new().with(view);
+    for view_type in config.mirror_views() {
+        let mirror = view_of_type(*view_type, config, recorder, events.clone())?;
+        multi_view = multi_view.with_threaded(mirror, MIRROR_QUEUE_CAPACITY);
+    }
+    if let Some(exporter) = config.export() {
+        let export_view = Box::new(ExportView::new(exporter.clone()));
+        multi_view = multi_view.with_threaded(export_view, EXPORT_QUEUE_CAPACITY);
     }
+    Ok(Box::new(multi_view))
 }
+
+//the web view sends the keys pressed in the browser as events,
+//and fails if its address is already in use
+fn view_of_type(
+    view_type: ViewType,
+    config: &Config,
+    recorder: &Recorder,
+    events: Sender<Event>,
+) -> io::Result<Box<dyn View>> {
+    Ok(match view_type {
+        ViewType::GUI => {
+            let output = config
+                .image_output()
+                .cloned()
+                .unwrap_or_else(|| PathBuf::from("frames"));
+            Box::new(ImageView::new(
+                ImageOutput::from_path(output),
+                DEFAULT_CELL_SIZE,
+            ))
+        }
+        ViewType::TUI => Box::new(tui_view(
+            config,
+            ColorSupport::detect(),
+            recorder.writer(io::stdout()),
+        )),
+        ViewType::Narrated => Box::new(NarratedView::new()),
+        ViewType::Web => Box::new(WebView::bind(config.web_addr(), events)?),
+    })
+}
+
+//the terminal view as the config asks for it, with the colors the terminal can show
+fn tui_view<W: Write>(config: &Config, color_support: ColorSupport, out: W) -> TuiView<W> {
+    TuiView::with_writer(out)
+        .with_color_support(color_support)
+        .with_glyphs(config.glyphs())
+        .with_render_mode(config.render_mode())
+        .with_camera(ScreenSize::Terminal, config.camera_dead_zone())
+        .with_minimap(config.minimap())
+}
+
+#[cfg(test)]
+mod tests;
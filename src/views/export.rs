@@ -0,0 +1,366 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, BufRead, BufWriter},
+    mem,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    locale::Locale,
+    model::{
+        render_state::{RenderState, Screen},
+        CellContent, GameState, Orientation, Snapshot,
+    },
+};
+
+use super::{
+    image_view::{render_grid, Palette, Rgb, DEFAULT_CELL_SIZE},
+    tiles::{self, TileShape},
+    Theme, View,
+};
+
+pub const DEFAULT_FPS: u32 = 8;
+
+//where a game is exported to: an animated gif, a single svg of its last
+//frame, or a directory filled with an svg for every frame
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportOutput {
+    Gif(PathBuf),
+    Snapshot(PathBuf),
+    Sequence(PathBuf),
+}
+
+impl ExportOutput {
+    //paths ending in .gif or .svg are single files, anything else is a directory for the sequence
+    pub fn from_path(path: PathBuf) -> Self {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_ascii_lowercase());
+        match extension.as_ref().and_then(|extension| extension.to_str()) {
+            Some("gif") => ExportOutput::Gif(path),
+            Some("svg") => ExportOutput::Snapshot(path),
+            _ => ExportOutput::Sequence(path),
+        }
+    }
+}
+
+//renders the frames of a game with the tiles of the graphical views,
+//every frame shown for 1/fps seconds
+#[derive(Clone, Debug, PartialEq)]
+pub struct Exporter {
+    output: ExportOutput,
+    cell_size: usize,
+    fps: u32,
+}
+
+impl Exporter {
+    pub fn new(output: ExportOutput) -> Self {
+        Exporter {
+            output,
+            cell_size: DEFAULT_CELL_SIZE,
+            fps: DEFAULT_FPS,
+        }
+    }
+
+    pub fn with_cell_size(mut self, cell_size: usize) -> Self {
+        self.cell_size = cell_size.max(1);
+        self
+    }
+
+    pub fn with_fps(mut self, fps: u32) -> Self {
+        self.fps = fps.max(1);
+        self
+    }
+
+    pub fn output(&self) -> &ExportOutput {
+        &self.output
+    }
+
+    pub fn export(&self, frames: &[Vec<Vec<CellContent>>], theme: &Theme) -> io::Result<()> {
+        let palette = Palette::for_theme(theme);
+        match &self.output {
+            ExportOutput::Gif(path) => {
+                create_parent(path)?;
+                write_gif(path, frames, self.cell_size, self.fps, &palette)
+            }
+            ExportOutput::Snapshot(path) => {
+                let Some(grid) = frames.last() else {
+                    return Ok(());
+                };
+                create_parent(path)?;
+                fs::write(path, svg_frame(grid, self.cell_size, &palette))
+            }
+            ExportOutput::Sequence(dir) => {
+                fs::create_dir_all(dir)?;
+                for (i, grid) in frames.iter().enumerate() {
+                    let path = dir.join(format!("frame_{i:06}.svg"));
+                    fs::write(path, svg_frame(grid, self.cell_size, &palette))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn create_parent(path: &Path) -> io::Result<()> {
+    path.parent().map_or(Ok(()), fs::create_dir_all)
+}
+
+//the frames share a palette with the few colors a board is drawn with
+pub fn write_gif(
+    path: &Path,
+    frames: &[Vec<Vec<CellContent>>],
+    cell_size: usize,
+    fps: u32,
+    palette: &Palette,
+) -> io::Result<()> {
+    let colors = [
+        palette.background,
+        palette.wall,
+        palette.head,
+        palette.body,
+        palette.tail,
+        palette.apple,
+    ];
+    let images: Vec<_> = frames
+        .iter()
+        .map(|grid| render_grid(grid, cell_size, palette))
+        .collect();
+    let Some(first) = images.first() else {
+        return Ok(());
+    };
+    let size =
+        |n: usize| u16::try_from(n).map_err(|_| io::Error::other("the board is too big for a gif"));
+    let (width, height) = (size(first.width())?, size(first.height())?);
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder =
+        gif::Encoder::new(file, width, height, colors.as_flattened()).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    //in hundredths of a second, which most players do not go below 2 of
+    let delay = (100 / fps).max(2) as u16;
+    for image in &images {
+        let indices: Vec<u8> = image
+            .pixels()
+            .iter()
+            .map(|pixel| colors.iter().position(|color| color == pixel).unwrap_or(0) as u8)
+            .collect();
+        let frame = gif::Frame {
+            width,
+            height,
+            delay,
+            buffer: indices.into(),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+//the board as render_grid draws it, with a wall half a cell wide,
+//but with shapes instead of pixels
+pub fn svg_frame(grid: &[Vec<CellContent>], cell_size: usize, palette: &Palette) -> String {
+    let rows = grid.len();
+    let columns = grid.first().map_or(0, |row| row.len());
+    let s = cell_size as f64;
+    let margin = (cell_size / 2) as f64;
+    let (width, height) = (
+        columns as f64 * s + 2.0 * margin,
+        rows as f64 * s + 2.0 * margin,
+    );
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        number(width),
+        number(height)
+    );
+    svg += &rect((0.0, 0.0), (width, height), palette.wall);
+    svg += &rect(
+        (margin, margin),
+        (columns as f64 * s, rows as f64 * s),
+        palette.background,
+    );
+    for (y, row) in grid.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            let origin = (margin + x as f64 * s, margin + y as f64 * s);
+            svg += &svg_tile(origin, s, cell, palette);
+        }
+    }
+    svg + "</svg>\n"
+}
+
+//the same shapes as draw_tile, strokes a third of the cell wide
+fn svg_tile((x0, y0): (f64, f64), s: f64, cell: &CellContent, palette: &Palette) -> String {
+    let Some((color, shapes)) = tiles::tile(cell) else {
+        return String::new();
+    };
+    let color = palette.tile_color(color);
+    let half = s / 2.0;
+    let stroke = s / 6.0;
+
+    let mut svg = String::new();
+    for shape in shapes {
+        svg += &match shape {
+            TileShape::Stroke(orientation) => {
+                let ((x, y), (width, height)) = match orientation {
+                    Orientation::Up => ((half - stroke, 0.0), (2.0 * stroke, half + stroke)),
+                    Orientation::Down => (
+                        (half - stroke, half - stroke),
+                        (2.0 * stroke, half + stroke),
+                    ),
+                    Orientation::Left => ((0.0, half - stroke), (half + stroke, 2.0 * stroke)),
+                    Orientation::Right => (
+                        (half - stroke, half - stroke),
+                        (half + stroke, 2.0 * stroke),
+                    ),
+                };
+                rect((x0 + x, y0 + y), (width, height), color)
+            }
+            TileShape::Arrow(orientation) => {
+                let points = match orientation {
+                    Orientation::Up => [(half, 0.0), (0.0, s), (s, s)],
+                    Orientation::Down => [(half, s), (s, 0.0), (0.0, 0.0)],
+                    Orientation::Left => [(0.0, half), (s, s), (s, 0.0)],
+                    Orientation::Right => [(s, half), (0.0, 0.0), (0.0, s)],
+                };
+                let points = points
+                    .map(|(x, y)| format!("{},{}", number(x0 + x), number(y0 + y)))
+                    .join(" ");
+                format!("<polygon points=\"{points}\" fill=\"{}\"/>\n", hex(color))
+            }
+            TileShape::Dot => format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+                number(x0 + half),
+                number(y0 + half),
+                number(s * 0.35),
+                hex(color)
+            ),
+        };
+    }
+    svg
+}
+
+fn rect((x, y): (f64, f64), (width, height): (f64, f64), color: Rgb) -> String {
+    format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+        number(x),
+        number(y),
+        number(width),
+        number(height),
+        hex(color)
+    )
+}
+
+//to the hundredth of a pixel, without trailing zeros
+fn number(n: f64) -> String {
+    let n = format!("{n:.2}");
+    n.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn hex([r, g, b]: Rgb) -> String {
+    let mut hex = String::from("#");
+    for component in [r, g, b] {
+        let _ = write!(hex, "{component:02x}");
+    }
+    hex
+}
+
+//the frames of the last game in the snapshots a remote client received,
+//one json object per line
+pub fn replay_frames(reader: impl BufRead) -> io::Result<Vec<Vec<Vec<CellContent>>>> {
+    let mut frames: Vec<Vec<Vec<CellContent>>> = Vec::new();
+    let mut game_over = false;
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let snapshot: Snapshot = serde_json::from_str(&line).map_err(io::Error::other)?;
+        if snapshot.snake.is_empty() {
+            continue;
+        }
+        let over = snapshot.state == GameState::GameOver;
+        //a new game after the last one ended
+        if game_over && !over {
+            frames.clear();
+        }
+        game_over = over;
+        let grid = snapshot
+            .grid()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if frames.last() != Some(&grid) {
+            frames.push(grid);
+        }
+    }
+    Ok(frames)
+}
+
+//a view with no display, keeping every frame of a game
+//to export it once the game is over
+pub struct ExportView {
+    exporter: Exporter,
+    theme: RefCell<Theme>,
+    frames: RefCell<Vec<Vec<Vec<CellContent>>>>,
+    //the game was exported, the end screens shown after it are not part of it
+    exported: Cell<bool>,
+}
+
+impl ExportView {
+    pub fn new(exporter: Exporter) -> Self {
+        ExportView {
+            exporter,
+            theme: RefCell::new(Theme::default()),
+            frames: RefCell::new(Vec::new()),
+            exported: Cell::new(false),
+        }
+    }
+}
+
+impl View for ExportView {
+    fn set_theme(&self, theme: &Theme) {
+        *self.theme.borrow_mut() = theme.clone();
+    }
+
+    //the exported frames have no text
+    fn set_locale(&self, _locale: Locale) {}
+
+    //every game overwrites the export of the previous one,
+    //and a game left for the title screen is not exported
+    fn render(&self, state: &RenderState) {
+        if let Screen::Title { .. } = state.screen {
+            self.frames.borrow_mut().clear();
+        }
+        let Some(board) = state.board() else {
+            return;
+        };
+        let ended = matches!(
+            state.screen,
+            Screen::GameOver { .. } | Screen::Victory(_) | Screen::NewHighScore { .. }
+        );
+        if self.exported.get() {
+            if ended {
+                return;
+            }
+            self.exported.set(false);
+        }
+        let mut frames = self.frames.borrow_mut();
+        //the menus and the animations draw the same board again
+        if frames.last() != Some(&board.grid) {
+            frames.push(board.grid.clone());
+        }
+        if ended {
+            self.exported.set(true);
+            let frames = mem::take(&mut *frames);
+            if let Err(e) = self.exporter.export(&frames, &self.theme.borrow()) {
+                eprintln!("failed to export the game: {e}\r");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,148 @@
+use std::{
+    env, fs,
+    io::{self, BufReader},
+    path::PathBuf,
+    process,
+};
+
+use super::{replay_frames, svg_frame, ExportOutput, ExportView, Exporter};
+use crate::{
+    model::{
+        render_state::{Board, RenderState, Screen},
+        CellContent, DeathCause, GameState, Hud, Orientation, Snapshot,
+    },
+    settings::GameMode,
+    views::{image_view::Palette, Theme, View},
+};
+
+fn export_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("rusty-rattle-export-{}-{name}", process::id()))
+}
+
+//a snake of three cells going right, on a 4x3 board
+fn snapshot(state: GameState, head_x: usize) -> Snapshot {
+    Snapshot {
+        state,
+        width: 4,
+        height: 3,
+        score: 0,
+        length: 3,
+        heading: Some(Orientation::Right),
+        snake: vec![(head_x, 1), (head_x - 1, 1), (head_x - 2, 1)],
+        apple: Some((0, 0)),
+    }
+}
+
+#[test]
+fn test_snapshot_grid() {
+    let mut snapshot = snapshot(GameState::Playing, 2);
+    //turning down, and across the bottom edge in wrap mode
+    snapshot.snake = vec![(1, 0), (1, 2), (0, 2)];
+    snapshot.heading = Some(Orientation::Down);
+    let grid = snapshot.grid().unwrap();
+    assert_eq!(grid[0][1], CellContent::Head(Orientation::Down));
+    assert_eq!(
+        grid[2][1],
+        CellContent::Body {
+            towards: Orientation::Down,
+            from: Orientation::Right,
+        }
+    );
+    assert_eq!(grid[2][0], CellContent::Tail(Orientation::Right));
+    assert_eq!(grid[0][0], CellContent::Apple);
+}
+
+#[test]
+fn test_replay_the_last_game() {
+    let lines = [
+        snapshot(GameState::Playing, 2),
+        snapshot(GameState::GameOver, 3),
+        snapshot(GameState::Playing, 2),
+        snapshot(GameState::Paused, 2),
+        snapshot(GameState::Playing, 3),
+    ]
+    .map(|snapshot| serde_json::to_string(&snapshot).unwrap())
+    .join("\n");
+    let frames = replay_frames(BufReader::new(lines.as_bytes())).unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[1][1][3], CellContent::Head(Orientation::Right));
+
+    assert!(replay_frames(BufReader::new("not json".as_bytes())).is_err());
+}
+
+#[test]
+fn test_replay_rejects_cells_outside_the_board() {
+    let mut outside = snapshot(GameState::Playing, 2);
+    outside.snake[0] = (outside.width, 0);
+    let mut no_board = snapshot(GameState::Playing, 2);
+    no_board.width = 0;
+    for snapshot in [outside, no_board] {
+        let line = serde_json::to_string(&snapshot).unwrap();
+        let error = replay_frames(BufReader::new(line.as_bytes())).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn test_svg_uses_the_tile_shapes() {
+    let grid = snapshot(GameState::Playing, 2).grid().unwrap();
+    let palette = Palette::for_theme(&Theme::default());
+    let svg = svg_frame(&grid, 12, &palette);
+    //the board with a wall half a cell wide
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"60\" height=\"48\""));
+    //the head is an arrow pointing right, from the cell at (2, 1)
+    assert!(svg.contains("<polygon points=\"42,24 30,18 30,30\""));
+    //the apple is a dot in the middle of its cell
+    assert!(svg.contains("<circle cx=\"12\" cy=\"12\" r=\"4.2\""));
+    //the tail is a stroke a third of the cell wide
+    assert!(svg.contains("<rect x=\"10\" y=\"22\" width=\"8\" height=\"4\""));
+    assert!(svg.ends_with("</svg>\n"));
+}
+
+#[test]
+fn test_export_view_writes_a_gif_when_the_game_is_over() {
+    let path = export_path("game.gif");
+    let exporter = Exporter::new(ExportOutput::from_path(path.clone())).with_fps(10);
+    let view = ExportView::new(exporter.clone().with_cell_size(4));
+    let board = |head_x| Board {
+        grid: snapshot(GameState::Playing, head_x).grid().unwrap(),
+        hud: Hud::default(),
+        effects: Vec::new(),
+        mode: GameMode::Classic,
+    };
+    for head_x in [2, 2] {
+        view.render(&RenderState::new(Screen::Playing(board(head_x))));
+    }
+    view.render(&RenderState::new(Screen::NewHighScore {
+        board: board(3),
+        rank: 0,
+        initials: String::new(),
+    }));
+    //the panels shown after the end don't export the game again
+    view.render(&RenderState::new(Screen::GameOver {
+        board: board(3),
+        cause: DeathCause::Wall,
+    }));
+
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+    let mut decoder = options.read_info(fs::File::open(&path).unwrap()).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (20, 16));
+    let mut frames = 0;
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        assert_eq!(frame.delay, 10);
+        frames += 1;
+    }
+    //the same board drawn twice is a single frame
+    assert_eq!(frames, 2);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        ExportOutput::from_path("frames".into()),
+        ExportOutput::Sequence("frames".into())
+    );
+    assert_eq!(
+        ExportOutput::from_path("bug.SVG".into()),
+        ExportOutput::Snapshot("bug.SVG".into())
+    );
+}
@@ -0,0 +1,192 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{CellContent, Orientation};
+
+//how the text views draw the board, every glyph takes two columns
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GlyphSet {
+    //for terminals without unicode support
+    Ascii,
+    #[default]
+    Unicode,
+    Emoji,
+}
+
+//the walls around the board, drawn as
+//  top_left horizontal... top_right
+//  vertical  cells...     vertical
+//  bottom_left horizontal... bottom_right
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Border {
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+    //the walls on the sides where the board goes on beyond the screen
+    pub open_horizontal: &'static str,
+    pub open_vertical: &'static str,
+}
+
+//the two edges of the cell a piece of body connects
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BodyShape {
+    Vertical,
+    Horizontal,
+    UpRight,
+    UpLeft,
+    DownRight,
+    DownLeft,
+}
+
+impl BodyShape {
+    //the snake comes in from the edge opposite to where it was going
+    //and leaves from the edge it is going towards
+    fn new(towards: Orientation, from: Orientation) -> Self {
+        use Orientation::*;
+        match (from.opposite(), towards) {
+            (Up, Down) | (Down, Up) => BodyShape::Vertical,
+            (Left, Right) | (Right, Left) => BodyShape::Horizontal,
+            (Up, Right) | (Right, Up) => BodyShape::UpRight,
+            (Up, Left) | (Left, Up) => BodyShape::UpLeft,
+            (Down, Right) | (Right, Down) => BodyShape::DownRight,
+            (Down, Left) | (Left, Down) => BodyShape::DownLeft,
+            (Up, Up) | (Down, Down) | (Left, Left) | (Right, Right) => {
+                panic!("impossible snake orientation {:?}", (from, towards))
+            }
+        }
+    }
+}
+
+impl GlyphSet {
+    pub const ALL: [GlyphSet; 3] = [GlyphSet::Ascii, GlyphSet::Unicode, GlyphSet::Emoji];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GlyphSet::Ascii => "ascii",
+            GlyphSet::Unicode => "unicode",
+            GlyphSet::Emoji => "emoji",
+        }
+    }
+
+    pub fn cell(&self, cell: &CellContent) -> &'static str {
+        match cell {
+            CellContent::Empty => "  ",
+            CellContent::Head(orientation) => self.head(*orientation),
+            CellContent::Tail(orientation) => self.tail(*orientation),
+            CellContent::Body { towards, from } => self.body(BodyShape::new(*towards, *from)),
+            CellContent::Apple => match self {
+                GlyphSet::Ascii => "@ ",
+                GlyphSet::Unicode => "● ",
+                GlyphSet::Emoji => "🍎",
+            },
+        }
+    }
+
+    //the ring drawn around an apple just eaten
+    pub fn pulse(&self) -> &'static str {
+        match self {
+            GlyphSet::Ascii => "* ",
+            GlyphSet::Unicode => "· ",
+            GlyphSet::Emoji => "✨",
+        }
+    }
+
+    fn head(&self, orientation: Orientation) -> &'static str {
+        match (self, orientation) {
+            (GlyphSet::Ascii, Orientation::Up) => "^ ",
+            (GlyphSet::Ascii, Orientation::Right) => "> ",
+            (GlyphSet::Ascii, Orientation::Down) => "v ",
+            (GlyphSet::Ascii, Orientation::Left) => " <",
+            (GlyphSet::Unicode, Orientation::Up) => "▲ ",
+            (GlyphSet::Unicode, Orientation::Right) => "▶ ",
+            (GlyphSet::Unicode, Orientation::Down) => "▼ ",
+            (GlyphSet::Unicode, Orientation::Left) => " ◀",
+            (GlyphSet::Emoji, _) => "🐍",
+        }
+    }
+
+    //the tail points towards the rest of the body
+    fn tail(&self, orientation: Orientation) -> &'static str {
+        match (self, orientation) {
+            (GlyphSet::Ascii, Orientation::Up) => "' ",
+            (GlyphSet::Ascii, Orientation::Right) => " -",
+            (GlyphSet::Ascii, Orientation::Down) => ". ",
+            (GlyphSet::Ascii, Orientation::Left) => "- ",
+            (GlyphSet::Unicode, Orientation::Up) => "╵ ",
+            (GlyphSet::Unicode, Orientation::Right) => " ╶",
+            (GlyphSet::Unicode, Orientation::Down) => "╷ ",
+            (GlyphSet::Unicode, Orientation::Left) => "╴ ",
+            (GlyphSet::Emoji, _) => "🟢",
+        }
+    }
+
+    fn body(&self, shape: BodyShape) -> &'static str {
+        match (self, shape) {
+            (GlyphSet::Ascii, BodyShape::Vertical) => "| ",
+            (GlyphSet::Ascii, BodyShape::Horizontal) => "--",
+            (GlyphSet::Ascii, BodyShape::UpRight | BodyShape::DownRight) => "+-",
+            (GlyphSet::Ascii, BodyShape::UpLeft | BodyShape::DownLeft) => "+ ",
+            (GlyphSet::Unicode, BodyShape::Vertical) => "┃ ",
+            (GlyphSet::Unicode, BodyShape::Horizontal) => "━━",
+            (GlyphSet::Unicode, BodyShape::UpRight) => "┗━",
+            (GlyphSet::Unicode, BodyShape::UpLeft) => "┛ ",
+            (GlyphSet::Unicode, BodyShape::DownRight) => "┏━",
+            (GlyphSet::Unicode, BodyShape::DownLeft) => "┓ ",
+            (GlyphSet::Emoji, _) => "🟩",
+        }
+    }
+
+    pub fn border(&self) -> Border {
+        match self {
+            GlyphSet::Ascii => Border {
+                top_left: "+-",
+                top_right: "+ ",
+                bottom_left: "+-",
+                bottom_right: "+ ",
+                horizontal: "--",
+                vertical: "| ",
+                open_horizontal: "..",
+                open_vertical: ": ",
+            },
+            GlyphSet::Unicode => Border {
+                top_left: "╔═",
+                top_right: "╗ ",
+                bottom_left: "╚═",
+                bottom_right: "╝ ",
+                horizontal: "══",
+                vertical: "║ ",
+                open_horizontal: "┄┄",
+                open_vertical: "┆ ",
+            },
+            GlyphSet::Emoji => Border {
+                top_left: "🧱",
+                top_right: "🧱",
+                bottom_left: "🧱",
+                bottom_right: "🧱",
+                horizontal: "🧱",
+                vertical: "🧱",
+                open_horizontal: "🟫",
+                open_vertical: "🟫",
+            },
+        }
+    }
+}
+
+impl FromStr for GlyphSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GlyphSet::ALL
+            .into_iter()
+            .find(|glyphs| glyphs.name() == s.to_lowercase())
+            .ok_or_else(|| format!("unknown glyph set {s:?}, expected ascii, unicode or emoji"))
+    }
+}
+
+#[cfg(test)]
+mod tests;
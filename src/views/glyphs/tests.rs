@@ -0,0 +1,63 @@
+use super::GlyphSet;
+use crate::model::{CellContent, Orientation};
+
+#[test]
+fn test_ascii_glyphs() {
+    let border = GlyphSet::Ascii.border();
+    let glyphs = CellContent::Empty
+        .into_iter()
+        .map(|cell| GlyphSet::Ascii.cell(cell))
+        .chain([
+            GlyphSet::Ascii.pulse(),
+            border.top_left,
+            border.top_right,
+            border.bottom_left,
+            border.bottom_right,
+            border.horizontal,
+            border.vertical,
+            border.open_horizontal,
+            border.open_vertical,
+        ]);
+    for glyph in glyphs {
+        assert!(glyph.is_ascii(), "{glyph:?} is not ascii");
+        assert_eq!(glyph.len(), 2, "{glyph:?} is not two columns wide");
+    }
+}
+
+#[test]
+fn test_body_corners() {
+    //going up and then turning right connects the bottom and the right edges
+    let body = |towards, from| CellContent::Body { towards, from };
+    assert_eq!(
+        GlyphSet::Unicode.cell(&body(Orientation::Right, Orientation::Up)),
+        "┏━"
+    );
+    assert_eq!(
+        GlyphSet::Unicode.cell(&body(Orientation::Down, Orientation::Left)),
+        "┏━"
+    );
+    assert_eq!(
+        GlyphSet::Unicode.cell(&body(Orientation::Left, Orientation::Down)),
+        "┛ "
+    );
+    assert_eq!(
+        GlyphSet::Ascii.cell(&body(Orientation::Left, Orientation::Left)),
+        "--"
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_impossible_body() {
+    GlyphSet::Unicode.cell(&CellContent::Body {
+        towards: Orientation::Up,
+        from: Orientation::Down,
+    });
+}
+
+#[test]
+fn test_glyph_set_from_str() {
+    assert_eq!("ASCII".parse(), Ok(GlyphSet::Ascii));
+    assert_eq!("emoji".parse(), Ok(GlyphSet::Emoji));
+    assert!("braille".parse::<GlyphSet>().is_err());
+}
@@ -0,0 +1,338 @@
+use std::{
+    cell::{Cell, RefCell},
+    fs::{self, File},
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    locale::Locale,
+    model::{
+        animation::Effect,
+        render_state::{RenderState, Screen},
+        CellContent, Orientation,
+    },
+};
+
+use super::{
+    theme::{Color, Role},
+    tiles::{self, TileColor, TileShape},
+    Theme, View,
+};
+
+pub type Rgb = [u8; 3];
+
+const BACKGROUND: Rgb = [0, 0, 0];
+//what the terminal would draw roles without a color with
+const FOREGROUND: Rgb = [255, 255, 255];
+
+pub const DEFAULT_CELL_SIZE: usize = 16;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    //fills the pixels in [x0, x1) x [y0, y1), clipped to the image
+    pub fn fill_rect(&mut self, (x0, y0): (usize, usize), (x1, y1): (usize, usize), color: Rgb) {
+        for y in y0..y1.min(self.height) {
+            for x in x0..x1.min(self.width) {
+                self.pixels[y * self.width + x] = color;
+            }
+        }
+    }
+
+    //fills the pixels whose center is inside the shape
+    fn fill_where(
+        &mut self,
+        (x0, y0): (usize, usize),
+        size: usize,
+        color: Rgb,
+        inside: impl Fn(f64, f64) -> bool,
+    ) {
+        for y in y0..(y0 + size).min(self.height) {
+            for x in x0..(x0 + size).min(self.width) {
+                let px = (x - x0) as f64 + 0.5;
+                let py = (y - y0) as f64 + 0.5;
+                if inside(px, py) {
+                    self.pixels[y * self.width + x] = color;
+                }
+            }
+        }
+    }
+
+    pub fn write_png(&self, path: &Path) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(self.pixels.as_flattened())
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+    pub background: Rgb,
+    pub head: Rgb,
+    pub body: Rgb,
+    pub tail: Rgb,
+    pub apple: Rgb,
+    pub wall: Rgb,
+}
+
+impl Palette {
+    pub fn for_theme(theme: &Theme) -> Self {
+        let rgb = |role| theme.color(role).map_or(FOREGROUND, Color::rgb);
+        Palette {
+            background: BACKGROUND,
+            head: rgb(Role::Head),
+            body: rgb(Role::Body),
+            tail: rgb(Role::Tail),
+            apple: rgb(Role::Apple),
+            wall: rgb(Role::Border),
+        }
+    }
+
+    pub fn tile_color(&self, color: TileColor) -> Rgb {
+        match color {
+            TileColor::Head => self.head,
+            TileColor::Body => self.body,
+            TileColor::Tail => self.tail,
+            TileColor::Apple => self.apple,
+        }
+    }
+}
+
+//draws the board with a wall around it, half a cell wide
+pub fn render_grid(grid: &[Vec<CellContent>], cell_size: usize, palette: &Palette) -> Image {
+    let rows = grid.len();
+    let columns = grid.first().map_or(0, |row| row.len());
+    let margin = cell_size / 2;
+    let mut image = Image::new(
+        columns * cell_size + 2 * margin,
+        rows * cell_size + 2 * margin,
+        palette.wall,
+    );
+    image.fill_rect(
+        (margin, margin),
+        (margin + columns * cell_size, margin + rows * cell_size),
+        palette.background,
+    );
+
+    for (y, row) in grid.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            let origin = (margin + x * cell_size, margin + y * cell_size);
+            draw_tile(&mut image, origin, cell_size, cell, palette);
+        }
+    }
+    image
+}
+
+pub fn draw_tile(
+    image: &mut Image,
+    origin: (usize, usize),
+    size: usize,
+    cell: &CellContent,
+    palette: &Palette,
+) {
+    let Some((color, shapes)) = tiles::tile(cell) else {
+        return;
+    };
+    let color = palette.tile_color(color);
+    let s = size as f64;
+    let half = s / 2.0;
+    //strokes are a third of the cell wide
+    let stroke = s / 6.0;
+
+    for shape in shapes {
+        match shape {
+            TileShape::Stroke(orientation) => image.fill_where(origin, size, color, |x, y| {
+                let across = match orientation {
+                    Orientation::Up | Orientation::Down => (x - half).abs(),
+                    Orientation::Left | Orientation::Right => (y - half).abs(),
+                };
+                let along = match orientation {
+                    Orientation::Up => y <= half + stroke,
+                    Orientation::Down => y >= half - stroke,
+                    Orientation::Left => x <= half + stroke,
+                    Orientation::Right => x >= half - stroke,
+                };
+                across <= stroke && along
+            }),
+            TileShape::Arrow(orientation) => {
+                let [a, b, c] = match orientation {
+                    Orientation::Up => [(half, 0.0), (0.0, s), (s, s)],
+                    Orientation::Down => [(half, s), (s, 0.0), (0.0, 0.0)],
+                    Orientation::Left => [(0.0, half), (s, s), (s, 0.0)],
+                    Orientation::Right => [(s, half), (0.0, 0.0), (0.0, s)],
+                };
+                image.fill_where(origin, size, color, |x, y| in_triangle((x, y), a, b, c))
+            }
+            TileShape::Dot => {
+                let radius = s * 0.35;
+                image.fill_where(origin, size, color, |x, y| {
+                    (x - half).powi(2) + (y - half).powi(2) <= radius * radius
+                })
+            }
+        }
+    }
+}
+
+//the pulse is a ring of small dots in the apple color
+pub fn draw_effect(
+    image: &mut Image,
+    grid: &[Vec<CellContent>],
+    effect: &Effect,
+    cell_size: usize,
+    palette: &Palette,
+) {
+    let margin = cell_size / 2;
+    let half = cell_size as f64 / 2.0;
+    let radius = cell_size as f64 * 0.15;
+    for (x, y) in effect.cells(grid) {
+        let origin = (margin + x * cell_size, margin + y * cell_size);
+        image.fill_where(origin, cell_size, palette.apple, |x, y| {
+            (x - half).powi(2) + (y - half).powi(2) <= radius * radius
+        });
+    }
+}
+
+fn in_triangle(p: (f64, f64), a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> bool {
+    let side = |(x1, y1): (f64, f64), (x2, y2): (f64, f64)| {
+        (x2 - x1) * (p.1 - y1) - (y2 - y1) * (p.0 - x1)
+    };
+    let (d1, d2, d3) = (side(a, b), side(b, c), side(c, a));
+    let has_negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_negative && has_positive)
+}
+
+//where the image view writes its frames: a single png overwritten
+//with every frame, or a directory filled with numbered frames
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageOutput {
+    Snapshot(PathBuf),
+    Sequence(PathBuf),
+}
+
+impl ImageOutput {
+    //paths ending in .png are snapshots, anything else is a directory for the sequence
+    pub fn from_path(path: PathBuf) -> Self {
+        match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("png") => ImageOutput::Snapshot(path),
+            _ => ImageOutput::Sequence(path),
+        }
+    }
+}
+
+//a view with no display, rasterizing every frame into a png image
+pub struct ImageView {
+    output: ImageOutput,
+    cell_size: usize,
+    theme: RefCell<Theme>,
+    frame_count: Cell<u64>,
+}
+
+impl ImageView {
+    pub fn new(output: ImageOutput, cell_size: usize) -> Self {
+        ImageView {
+            output,
+            cell_size,
+            theme: RefCell::new(Theme::default()),
+            frame_count: Cell::new(0),
+        }
+    }
+
+    fn write_frame(&self, image: &Image) {
+        let path = match &self.output {
+            ImageOutput::Snapshot(path) => path.clone(),
+            ImageOutput::Sequence(dir) => {
+                let frame = self.frame_count.get();
+                self.frame_count.set(frame + 1);
+                dir.join(format!("frame_{frame:06}.png"))
+            }
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| image.write_png(&path));
+        if let Err(e) = result {
+            eprintln!("failed to write {}: {e}\r", path.display());
+        }
+    }
+}
+
+impl View for ImageView {
+    fn set_theme(&self, theme: &Theme) {
+        *self.theme.borrow_mut() = theme.clone();
+    }
+
+    //the images only show the board
+    fn set_locale(&self, _locale: Locale) {}
+
+    //there is no text to draw the menus and the hud with, and the pause
+    //does not change the board
+    fn render(&self, state: &RenderState) {
+        let mut palette = Palette::for_theme(&self.theme.borrow());
+        let board = match &state.screen {
+            Screen::Playing(board) | Screen::Menu { board, .. } | Screen::ResumeOffer(board) => {
+                board
+            }
+            //the last frame again, with the wall in the apple color
+            Screen::GameOver { board, .. } => {
+                palette.wall = palette.apple;
+                board
+            }
+            //or in the head color when the snake filled the board
+            Screen::Victory(board) => {
+                palette.wall = palette.head;
+                board
+            }
+            Screen::Title { .. }
+            | Screen::Controls { .. }
+            | Screen::Paused(_)
+            | Screen::NewHighScore { .. }
+            | Screen::HighScores { .. }
+            | Screen::Stats(_) => return,
+        };
+        let mut image = render_grid(&board.grid, self.cell_size, &palette);
+        for effect in &board.effects {
+            draw_effect(&mut image, &board.grid, effect, self.cell_size, &palette);
+        }
+        self.write_frame(&image);
+    }
+}
+
+#[cfg(test)]
+mod tests;
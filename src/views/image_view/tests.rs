@@ -0,0 +1,95 @@
+use std::{env, fs, path::PathBuf};
+
+use super::{render_grid, ImageOutput, Palette};
+use crate::{
+    model::{CellContent, Orientation},
+    views::Theme,
+};
+
+const CELL: usize = 12;
+const MARGIN: usize = CELL / 2;
+
+fn center_of(x: usize, y: usize) -> (usize, usize) {
+    (MARGIN + x * CELL + CELL / 2, MARGIN + y * CELL + CELL / 2)
+}
+
+fn test_grid() -> Vec<Vec<CellContent>> {
+    let mut grid = vec![vec![CellContent::Empty; 4]; 3];
+    grid[0][0] = CellContent::Apple;
+    grid[1][1] = CellContent::Head(Orientation::Right);
+    grid[1][0] = CellContent::Body {
+        towards: Orientation::Right,
+        from: Orientation::Up,
+    };
+    grid[2][0] = CellContent::Tail(Orientation::Up);
+    grid
+}
+
+#[test]
+fn test_render_grid() {
+    //the head and the body have different colors in this theme
+    let palette = Palette::for_theme(&Theme::named("colorblind").unwrap().unwrap());
+    assert_ne!(palette.head, palette.body);
+    let image = render_grid(&test_grid(), CELL, &palette);
+
+    assert_eq!(image.width(), 4 * CELL + 2 * MARGIN);
+    assert_eq!(image.height(), 3 * CELL + 2 * MARGIN);
+
+    //the wall goes around the board
+    assert_eq!(image.pixel(0, 0), palette.wall);
+    assert_eq!(
+        image.pixel(image.width() - 1, image.height() - 1),
+        palette.wall
+    );
+
+    let (x, y) = center_of(0, 0);
+    assert_eq!(image.pixel(x, y), palette.apple);
+    let (x, y) = center_of(3, 2);
+    assert_eq!(image.pixel(x, y), palette.background);
+
+    //the snake goes up and then turns right, so the corner connects the bottom and the right edges
+    let (x, y) = center_of(0, 1);
+    assert_eq!(image.pixel(x, y), palette.body);
+    assert_eq!(image.pixel(x, y + CELL / 2 - 1), palette.body);
+    assert_eq!(image.pixel(x + CELL / 2 - 1, y), palette.body);
+    assert_eq!(image.pixel(x, y - CELL / 2), palette.background);
+    assert_eq!(image.pixel(x - CELL / 2, y), palette.background);
+
+    //the head points right, so it narrows towards the right edge
+    let (x, y) = center_of(1, 1);
+    assert_eq!(image.pixel(x + CELL / 4, y), palette.head);
+    assert_eq!(image.pixel(x - CELL / 2, y - CELL / 2), palette.head);
+    assert_eq!(
+        image.pixel(x + CELL / 2 - 1, y - CELL / 2 + 1),
+        palette.background
+    );
+}
+
+#[test]
+fn test_write_png() {
+    let dir = env::temp_dir().join(format!("rusty-rattle-image-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("frame.png");
+
+    let image = render_grid(&test_grid(), CELL, &Palette::for_theme(&Theme::default()));
+    image.write_png(&path).unwrap();
+
+    let decoder = png::Decoder::new(std::io::BufReader::new(fs::File::open(&path).unwrap()));
+    let reader = decoder.read_info().unwrap();
+    assert_eq!(reader.info().width as usize, image.width());
+    assert_eq!(reader.info().height as usize, image.height());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_image_output_from_path() {
+    assert_eq!(
+        ImageOutput::from_path(PathBuf::from("shot.PNG")),
+        ImageOutput::Snapshot(PathBuf::from("shot.PNG"))
+    );
+    assert_eq!(
+        ImageOutput::from_path(PathBuf::from("frames")),
+        ImageOutput::Sequence(PathBuf::from("frames"))
+    );
+}
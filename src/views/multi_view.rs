@@ -0,0 +1,184 @@
+use std::{
+    mem,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, SyncSender, TrySendError},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+use crate::{locale::Locale, model::render_state::RenderState};
+
+use super::{Theme, View};
+
+//a View call with everything it borrows, to send it to another thread
+#[derive(Clone)]
+enum Call {
+    SetTheme(Theme),
+    SetLocale(Locale),
+    Render(RenderState),
+}
+
+impl Call {
+    fn apply(self, view: &dyn View) {
+        match self {
+            Call::SetTheme(theme) => view.set_theme(&theme),
+            Call::SetLocale(locale) => view.set_locale(locale),
+            Call::Render(state) => view.render(&state),
+        }
+    }
+}
+
+//the theme and language set since the worker last looked, only the latest of each counts
+#[derive(Default)]
+struct PendingSettings {
+    theme: Option<Theme>,
+    locale: Option<Locale>,
+}
+
+impl PendingSettings {
+    fn apply(pending: &Mutex<PendingSettings>, view: &dyn View) {
+        //taken out first, so the game never waits for the view to draw
+        let PendingSettings { theme, locale } = match pending.lock() {
+            Ok(mut pending) => mem::take(&mut *pending),
+            Err(_) => return,
+        };
+        if let Some(theme) = theme {
+            view.set_theme(&theme);
+        }
+        if let Some(locale) = locale {
+            view.set_locale(locale);
+        }
+    }
+}
+
+//a view drawing on its own thread, the frames it has no room for are dropped
+struct Worker {
+    sender: Option<SyncSender<Call>>,
+    handle: Option<JoinHandle<()>>,
+    dropped: Arc<AtomicU64>,
+    settings: Arc<Mutex<PendingSettings>>,
+}
+
+impl Worker {
+    fn spawn(view: Box<dyn View>, capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<Call>(capacity);
+        let settings = Arc::new(Mutex::new(PendingSettings::default()));
+        let pending = settings.clone();
+        let handle = thread::spawn(move || {
+            for call in receiver {
+                PendingSettings::apply(&pending, view.as_ref());
+                if let Call::Render(state) = call {
+                    view.render(&state);
+                }
+            }
+            //the ones set after the last frame
+            PendingSettings::apply(&pending, view.as_ref());
+        });
+        Worker {
+            sender: Some(sender),
+            handle: Some(handle),
+            dropped: Arc::new(AtomicU64::new(0)),
+            settings,
+        }
+    }
+
+    //never waits for the view: a missed frame is drawn over by the next one,
+    //while the theme and language wait in their slot for whatever the view gets next
+    fn send(&self, call: Call) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        if let Ok(mut pending) = self.settings.lock() {
+            match &call {
+                Call::SetTheme(theme) => pending.theme = Some(theme.clone()),
+                Call::SetLocale(locale) => pending.locale = Some(*locale),
+                Call::Render(_) => {}
+            }
+        }
+        let is_frame = matches!(call, Call::Render(_));
+        match sender.try_send(call) {
+            Err(TrySendError::Full(_)) if is_frame => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            //a view that panicked just stops drawing
+            _ => {}
+        }
+    }
+}
+
+//forwards every call to any number of views, the ones added with
+//with_threaded draw on their own thread so a slow one never stalls the game
+#[derive(Default)]
+pub struct MultiView {
+    views: Vec<Box<dyn View>>,
+    workers: Vec<Worker>,
+}
+
+impl MultiView {
+    pub fn new() -> Self {
+        MultiView::default()
+    }
+
+    //drawn before the call returns, like a view on its own
+    pub fn with(mut self, view: Box<dyn View>) -> Self {
+        self.views.push(view);
+        self
+    }
+
+    //drawn on a thread of its own, with room for capacity calls
+    //waiting to be drawn before the next frames are dropped
+    pub fn with_threaded(mut self, view: Box<dyn View>, capacity: usize) -> Self {
+        self.workers.push(Worker::spawn(view, capacity));
+        self
+    }
+
+    //how many frames each threaded view missed because it was too slow, in the order they were added
+    pub fn dropped(&self) -> Vec<u64> {
+        self.workers
+            .iter()
+            .map(|worker| worker.dropped.load(Ordering::Relaxed))
+            .collect()
+    }
+
+    fn forward(&self, call: Call) {
+        for worker in &self.workers {
+            worker.send(call.clone());
+        }
+        for view in &self.views {
+            call.clone().apply(view.as_ref());
+        }
+    }
+}
+
+//the threaded views finish drawing what they were sent before the game quits
+impl Drop for MultiView {
+    fn drop(&mut self) {
+        for worker in &mut self.workers {
+            worker.sender.take();
+        }
+        for worker in &mut self.workers {
+            if let Some(handle) = worker.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+impl View for MultiView {
+    fn set_theme(&self, theme: &Theme) {
+        self.forward(Call::SetTheme(theme.clone()));
+    }
+
+    fn set_locale(&self, locale: Locale) {
+        self.forward(Call::SetLocale(locale));
+    }
+
+    fn render(&self, state: &RenderState) {
+        self.forward(Call::Render(state.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,152 @@
+use std::sync::{
+    mpsc::{self, Receiver, Sender},
+    Arc, Mutex,
+};
+
+use super::MultiView;
+use crate::{
+    locale::Locale,
+    model::{
+        render_state::{Board, RenderState, Screen},
+        CellContent, DeathCause, Hud,
+    },
+    settings::GameMode,
+    views::{Theme, View},
+};
+
+//writes down the calls it gets, when it has a gate it tells every call
+//it got and waits for a go ahead first
+struct RecordingView {
+    calls: Arc<Mutex<Vec<String>>>,
+    gate: Option<Mutex<(Sender<()>, Receiver<()>)>>,
+}
+
+impl RecordingView {
+    fn new() -> (Self, Arc<Mutex<Vec<String>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let view = RecordingView {
+            calls: calls.clone(),
+            gate: None,
+        };
+        (view, calls)
+    }
+
+    fn record(&self, call: String) {
+        if let Some(gate) = &self.gate {
+            let (entered, go_ahead) = &*gate.lock().unwrap();
+            let _ = entered.send(());
+            let _ = go_ahead.recv();
+        }
+        self.calls.lock().unwrap().push(call);
+    }
+}
+
+impl View for RecordingView {
+    fn set_theme(&self, theme: &Theme) {
+        self.record(format!("theme {}", theme.name()));
+    }
+
+    fn set_locale(&self, locale: Locale) {
+        self.record(format!("locale {}", locale.code()));
+    }
+
+    fn render(&self, state: &RenderState) {
+        let call = match &state.screen {
+            Screen::Playing(board) => {
+                let (width, height) = board.size();
+                format!("frame {width}x{height} score {}", board.hud.score)
+            }
+            Screen::GameOver { board, .. } => format!("game over {}", board.hud.score),
+            screen => format!("{screen:?}"),
+        };
+        self.record(call);
+    }
+}
+
+fn draw_some(view: &MultiView, before_frame: impl FnOnce()) {
+    view.set_theme(&Theme::default());
+    before_frame();
+    let board = Board {
+        grid: vec![vec![CellContent::Empty; 3]; 2],
+        hud: Hud {
+            score: 10,
+            ..Hud::default()
+        },
+        effects: Vec::new(),
+        mode: GameMode::Classic,
+    };
+    view.render(&RenderState::new(Screen::Playing(board.clone())));
+    view.render(&RenderState::new(Screen::GameOver {
+        board,
+        cause: DeathCause::Wall,
+    }));
+}
+
+#[test]
+fn test_forward_to_every_view() {
+    let (inline, inline_calls) = RecordingView::new();
+    let (threaded, threaded_calls) = RecordingView::new();
+    let view = MultiView::new()
+        .with(Box::new(inline))
+        .with_threaded(Box::new(threaded), 8);
+    draw_some(&view, || {});
+    //the threaded views are done once the multi view is dropped
+    drop(view);
+
+    let expected = ["theme color", "frame 3x2 score 10", "game over 10"];
+    assert_eq!(*inline_calls.lock().unwrap(), expected);
+    assert_eq!(*threaded_calls.lock().unwrap(), expected);
+}
+
+#[test]
+fn test_slow_views_miss_calls_instead_of_stalling() {
+    let (entered_sender, entered) = mpsc::channel();
+    let (go_ahead, go_ahead_receiver) = mpsc::channel();
+    let (mut slow, slow_calls) = RecordingView::new();
+    slow.gate = Some(Mutex::new((entered_sender, go_ahead_receiver)));
+    let (inline, inline_calls) = RecordingView::new();
+    let view = MultiView::new()
+        .with(Box::new(inline))
+        .with_threaded(Box::new(slow), 1);
+
+    //the slow view holds the theme, has room for the frame and misses the game over
+    draw_some(&view, || entered.recv().unwrap());
+    assert_eq!(inline_calls.lock().unwrap().len(), 3);
+    assert_eq!(view.dropped(), [1]);
+
+    for _ in 0..2 {
+        go_ahead.send(()).unwrap();
+    }
+    drop(view);
+    assert_eq!(
+        *slow_calls.lock().unwrap(),
+        ["theme color", "frame 3x2 score 10"]
+    );
+}
+
+#[test]
+fn test_slow_views_never_miss_settings() {
+    let (entered_sender, entered) = mpsc::channel();
+    let (go_ahead, go_ahead_receiver) = mpsc::channel();
+    let (mut slow, slow_calls) = RecordingView::new();
+    slow.gate = Some(Mutex::new((entered_sender, go_ahead_receiver)));
+    let view = MultiView::new().with_threaded(Box::new(slow), 1);
+
+    //the slow view holds the theme and has room for the frame only
+    draw_some(&view, || entered.recv().unwrap());
+    assert_eq!(view.dropped(), [1]);
+
+    //the language doesn't wait for the stalled view, only the last one is kept for it
+    view.set_locale(Locale::English);
+    view.set_locale(Locale::Italian);
+    assert_eq!(view.dropped(), [1]);
+
+    for _ in 0..3 {
+        go_ahead.send(()).unwrap();
+    }
+    drop(view);
+    assert_eq!(
+        *slow_calls.lock().unwrap(),
+        ["theme color", "locale it", "frame 3x2 score 10"]
+    );
+}
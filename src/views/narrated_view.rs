@@ -0,0 +1,463 @@
+use std::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    io::{self, Stdout, Write},
+    time::Duration,
+};
+
+use crate::{
+    locale::{self, Locale, Messages},
+    model::{
+        neighbour,
+        render_state::{Board, RenderState, Screen},
+        title_menu::MenuEntry,
+        CellContent, DeathCause, Hud, Orientation,
+    },
+};
+
+use super::{Theme, View};
+
+//an obstacle this close ahead of the head is announced at every step
+const DANGER_DISTANCE: usize = 2;
+
+const DIRECTIONS: [Orientation; 4] = [
+    Orientation::Up,
+    Orientation::Right,
+    Orientation::Down,
+    Orientation::Left,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ObstacleKind {
+    Wall,
+    Body,
+    Tail,
+}
+
+impl ObstacleKind {
+    fn name(&self, messages: &Messages) -> &'static str {
+        match self {
+            ObstacleKind::Wall => messages.wall,
+            ObstacleKind::Body => messages.body,
+            ObstacleKind::Tail => messages.tail,
+        }
+    }
+}
+
+//what the head would run into going straight in a direction,
+//and in how many steps, None in wrap mode when nothing is in the way
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Obstacle {
+    kind: ObstacleKind,
+    steps: usize,
+}
+
+//what a frame shows, as far as the player needs to know
+#[derive(Clone, Debug, PartialEq)]
+struct Scene {
+    head: (usize, usize),
+    heading: Orientation,
+    apple: Option<(usize, usize)>,
+    length: usize,
+    //the game clock, which only moves while playing
+    elapsed: Duration,
+    //in the order of DIRECTIONS
+    obstacles: [Option<Obstacle>; 4],
+}
+
+impl Scene {
+    //there is no head to describe while the dead snake dissolves
+    fn new(board: &Board) -> Option<Scene> {
+        let grid = &board.grid;
+        let (head, heading) = find(grid, |cell| match cell {
+            CellContent::Head(orientation) => Some(*orientation),
+            _ => None,
+        })?;
+        let apple =
+            find(grid, |cell| (*cell == CellContent::Apple).then_some(())).map(|(apple, _)| apple);
+        Some(Scene {
+            head,
+            heading,
+            apple,
+            length: board.hud.length,
+            elapsed: board.hud.elapsed,
+            obstacles: DIRECTIONS.map(|direction| obstacle(board, head, direction)),
+        })
+    }
+
+    fn ahead(&self) -> Option<Obstacle> {
+        self.obstacle(self.heading)
+    }
+
+    fn obstacle(&self, direction: Orientation) -> Option<Obstacle> {
+        let index = DIRECTIONS.iter().position(|d| *d == direction);
+        self.obstacles[index.expect("every direction is in DIRECTIONS")]
+    }
+
+    //why the scene is worth announcing after the previous one, if it is
+    fn news(&self, previous: Option<&Scene>, messages: &Messages) -> Option<String> {
+        let Some(previous) = previous.filter(|previous| previous.length <= self.length) else {
+            //a new round
+            return Some(String::new());
+        };
+        let ahead = self.ahead();
+        if self.length > previous.length {
+            Some(locale::fill(
+                messages.apple_eaten,
+                &[("length", &self.length)],
+            ))
+        } else if self.heading != previous.heading {
+            Some(locale::fill(
+                messages.turned,
+                &[("direction", &messages.direction(self.heading))],
+            ))
+        } else if ahead != previous.ahead() {
+            //nothing is ever ahead on a clear line in wrap mode
+            let danger = ahead.filter(|ahead| ahead.steps <= DANGER_DISTANCE)?;
+            Some(locale::fill(
+                messages.danger,
+                &[
+                    ("obstacle", &danger.kind.name(messages)),
+                    ("steps", &danger.steps),
+                ],
+            ))
+        } else {
+            None
+        }
+    }
+
+    //e.g. heading up at column 16 row 11. apple 2 left 3 down.
+    //up wall in 11, right wall in 15, down body in 1, left wall in 16.
+    fn describe(&self, messages: &Messages) -> String {
+        let (x, y) = self.head;
+        let mut description = locale::fill(
+            messages.heading,
+            &[
+                ("direction", &messages.direction(self.heading)),
+                ("column", &(x + 1)),
+                ("row", &(y + 1)),
+            ],
+        );
+        if let Some((apple_x, apple_y)) = self.apple {
+            let cells = |template, cells: usize| locale::fill(template, &[("cells", &cells)]);
+            let across = match apple_x.cmp(&x) {
+                Ordering::Less => cells(messages.cells_left, x - apple_x),
+                Ordering::Equal => String::new(),
+                Ordering::Greater => cells(messages.cells_right, apple_x - x),
+            };
+            let down = match apple_y.cmp(&y) {
+                Ordering::Less => cells(messages.cells_up, y - apple_y),
+                Ordering::Equal => String::new(),
+                Ordering::Greater => cells(messages.cells_down, apple_y - y),
+            };
+            let apple = [across, down]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            description += &locale::fill(messages.apple_at, &[("position", &apple)]);
+        }
+        let obstacles = DIRECTIONS
+            .iter()
+            .zip(self.obstacles)
+            .map(|(direction, obstacle)| match obstacle {
+                Some(obstacle) => locale::fill(
+                    messages.obstacle_in,
+                    &[
+                        ("direction", &messages.direction(*direction)),
+                        ("obstacle", &obstacle.kind.name(messages)),
+                        ("steps", &obstacle.steps),
+                    ],
+                ),
+                None => locale::fill(
+                    messages.way_clear,
+                    &[("direction", &messages.direction(*direction))],
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        description + &obstacles + "."
+    }
+}
+
+fn find<T>(
+    grid: &[Vec<CellContent>],
+    matches: impl Fn(&CellContent) -> Option<T>,
+) -> Option<((usize, usize), T)> {
+    grid.iter().enumerate().find_map(|(y, row)| {
+        row.iter()
+            .enumerate()
+            .find_map(|(x, cell)| Some(((x, y), matches(cell)?)))
+    })
+}
+
+//in wrap mode the scan goes across the edges like the snake does,
+//and a line with nothing in it comes back around to the head
+fn obstacle(board: &Board, head: (usize, usize), direction: Orientation) -> Option<Obstacle> {
+    let mut position = head;
+    let mut steps = 1;
+    loop {
+        let Some(next) = neighbour(board.mode, board.size(), position, direction) else {
+            return Some(Obstacle {
+                kind: ObstacleKind::Wall,
+                steps,
+            });
+        };
+        if next == head {
+            return None;
+        }
+        let kind = match &board.grid[next.1][next.0] {
+            CellContent::Body { .. } | CellContent::Head(_) => ObstacleKind::Body,
+            CellContent::Tail(_) => ObstacleKind::Tail,
+            CellContent::Empty | CellContent::Apple => {
+                position = next;
+                steps += 1;
+                continue;
+            }
+        };
+        return Some(Obstacle { kind, steps });
+    }
+}
+
+//the selected entry of a menu, as it would be read out
+fn selected_entry(menu: &[MenuEntry]) -> String {
+    let Some(entry) = menu.iter().find(|entry| entry.selected) else {
+        return String::new();
+    };
+    let mut line = entry.label.to_string();
+    if !entry.value.is_empty() {
+        line += &format!(" {}", entry.value);
+    }
+    if let Some(error) = &entry.error {
+        line += &format!(", {error}");
+    }
+    line
+}
+
+//a view for screen readers, writing a line of plain text whenever
+//something the player should know about changes, instead of drawing
+pub struct NarratedView<W: Write = Stdout> {
+    out: RefCell<W>,
+    locale: Cell<Locale>,
+    scene: RefCell<Option<Scene>>,
+    //the last screen and menu entry announced, to only read the entries as they change,
+    //None while playing
+    menu: RefCell<Option<(String, String)>>,
+}
+
+impl Default for NarratedView {
+    fn default() -> Self {
+        NarratedView::with_writer(io::stdout())
+    }
+}
+
+impl NarratedView {
+    pub fn new() -> Self {
+        NarratedView::default()
+    }
+}
+
+impl<W: Write> NarratedView<W> {
+    pub fn with_writer(out: W) -> Self {
+        NarratedView {
+            out: RefCell::new(out),
+            locale: Cell::new(Locale::default()),
+            scene: RefCell::new(None),
+            menu: RefCell::new(None),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out.into_inner()
+    }
+
+    //like the text view, a writer that stopped accepting output just misses the lines
+    fn announce(&self, line: &str) {
+        let mut out = self.out.borrow_mut();
+        let _ = write!(out, "{line}\r\n").and_then(|_| out.flush());
+    }
+
+    //the whole screen the first time it is shown, then only the entry that changed
+    fn announce_menu(&self, screen: &str, intro: &str, menu: &[MenuEntry]) {
+        let selected = selected_entry(menu);
+        let mut last = self.menu.borrow_mut();
+        match last.as_ref() {
+            Some((last_screen, _)) if last_screen != screen => {
+                self.announce(&format!("{intro}{selected}"))
+            }
+            Some((_, last_selected)) if *last_selected == selected => {}
+            Some(_) => self.announce(&selected),
+            None => self.announce(&format!("{intro}{selected}")),
+        }
+        *last = Some((screen.to_string(), selected));
+    }
+
+    fn messages(&self) -> &'static Messages {
+        self.locale.get().messages()
+    }
+
+    fn narrate_board(&self, board: &Board) {
+        let Some(scene) = Scene::new(board) else {
+            return;
+        };
+        //the animations draw the same frame again
+        let mut last = self.scene.borrow_mut();
+        if last.as_ref() == Some(&scene) {
+            return;
+        }
+        let messages = self.messages();
+        //back from a menu or the pause, the player needs the whole picture again
+        let news = match self.menu.borrow_mut().take() {
+            Some(_) => Some(String::new()),
+            None => scene.news(last.as_ref(), messages),
+        };
+        if let Some(news) = news {
+            self.announce(&(news + &scene.describe(messages)));
+        }
+        *last = Some(scene);
+    }
+
+    //screens without entries are read once, until something else is shown
+    fn announce_once(&self, screen: &str, line: &str) {
+        let mut last = self.menu.borrow_mut();
+        if last
+            .as_ref()
+            .is_some_and(|(last_screen, _)| last_screen == screen)
+        {
+            return;
+        }
+        *last = Some((screen.to_string(), String::new()));
+        self.announce(line);
+    }
+
+    fn announce_end(&self, screen: &str, headline: &str, hud: &Hud) {
+        *self.scene.borrow_mut() = None;
+        let messages = self.messages();
+        let end = locale::fill(
+            messages.narrated_end,
+            &[
+                ("headline", &headline),
+                ("score", &hud.score),
+                ("length", &hud.length),
+                ("apples", &hud.apples),
+                ("seconds", &hud.elapsed.as_secs()),
+            ],
+        );
+        self.announce_once(screen, &format!("{end}{}.", messages.continue_or_quit));
+    }
+}
+
+impl<W: Write + Send> View for NarratedView<W> {
+    fn set_theme(&self, _theme: &Theme) {}
+
+    fn set_locale(&self, locale: Locale) {
+        self.locale.set(locale);
+    }
+
+    //the latency overlay is for developers looking at the board, so the debug lines are not read
+    fn render(&self, state: &RenderState) {
+        let messages = self.messages();
+        match &state.screen {
+            Screen::Title { menu, .. } => {
+                *self.scene.borrow_mut() = None;
+                self.announce_menu("title", messages.narrated_title, menu);
+            }
+            Screen::Controls { bindings, message } => {
+                self.announce_menu("controls", messages.narrated_controls, bindings);
+                if let Some(message) = message {
+                    self.announce(message);
+                }
+            }
+            Screen::Playing(board) => self.narrate_board(board),
+            Screen::Paused(_) => self.announce_once("paused", &format!("{}.", messages.paused)),
+            Screen::Menu { title, entries, .. } => {
+                self.announce_menu(title, &format!("{title}. "), entries)
+            }
+            Screen::GameOver { board, cause } => {
+                let cause = match cause {
+                    DeathCause::Wall => messages.hit_wall,
+                    DeathCause::Body => messages.hit_body,
+                };
+                let headline = format!("{}, {cause}", messages.narrated_game_over);
+                self.announce_end("game over", &headline, &board.hud);
+            }
+            Screen::Victory(board) => {
+                self.announce_end("victory", messages.narrated_victory, &board.hud)
+            }
+            //the prompt is read once, then the initials as they are typed
+            Screen::NewHighScore {
+                board,
+                rank,
+                initials,
+            } => {
+                *self.scene.borrow_mut() = None;
+                let line = match initials.as_str() {
+                    "" => format!(
+                        "{}, {}, {}. {}.",
+                        messages.new_high_score.to_lowercase(),
+                        locale::fill(messages.your_score, &[("score", &board.hud.score)]),
+                        locale::fill(messages.rank, &[("rank", &(rank + 1))]),
+                        messages.initials_hint
+                    ),
+                    _ => format!(
+                        "{}.",
+                        locale::fill(messages.your_initials, &[("initials", initials)])
+                    ),
+                };
+                self.announce_once(&format!("new high score {initials}"), &line);
+            }
+            Screen::HighScores { settings, scores } => {
+                let scores = scores
+                    .iter()
+                    .enumerate()
+                    .map(|(i, high_score)| {
+                        format!("{} {} {}", i + 1, high_score.initials, high_score.score)
+                    })
+                    .collect::<Vec<_>>();
+                let scores = if scores.is_empty() {
+                    messages.no_scores.to_string()
+                } else {
+                    scores.join(", ")
+                };
+                self.announce_once(
+                    "high scores",
+                    &format!(
+                        "{}, {settings}. {scores}. {}",
+                        messages.high_scores, messages.narrated_back
+                    ),
+                );
+            }
+            Screen::Stats(stats) => {
+                let lines: Vec<_> = stats
+                    .lines(messages)
+                    .into_iter()
+                    .map(|line| line.trim().to_string())
+                    .filter(|line| !line.is_empty())
+                    .collect();
+                self.announce_once(
+                    "stats",
+                    &format!(
+                        "{}. {}. {}",
+                        messages.lifetime_stats,
+                        lines.join(", "),
+                        messages.narrated_back
+                    ),
+                );
+            }
+            //the board is read from the start once the game is resumed
+            Screen::ResumeOffer(board) => {
+                *self.scene.borrow_mut() = None;
+                let line = format!(
+                    "{}, {}. {}.",
+                    messages.saved_game.to_lowercase(),
+                    locale::fill(messages.your_score, &[("score", &board.hud.score)]),
+                    messages.resume_hint
+                );
+                self.announce_once("saved game", &line);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use super::NarratedView;
+use crate::{
+    model::{
+        render_state::{Board, RenderState, Screen},
+        title_menu::MenuEntry,
+        CellContent, DeathCause, Hud, Orientation,
+    },
+    settings::GameMode,
+    views::View,
+};
+
+const BODY: CellContent = CellContent::Body {
+    towards: Orientation::Up,
+    from: Orientation::Up,
+};
+
+//a 5x5 board with the apple in the top left corner
+fn grid(snake: &[((usize, usize), CellContent)]) -> Vec<Vec<CellContent>> {
+    let mut grid = vec![vec![CellContent::Empty; 5]; 5];
+    grid[0][0] = CellContent::Apple;
+    for ((x, y), cell) in snake {
+        grid[*y][*x] = cell.clone();
+    }
+    grid
+}
+
+fn hud(length: usize, tick: u64) -> Hud {
+    Hud {
+        length,
+        elapsed: Duration::from_millis(200) * tick as u32,
+        ..Hud::default()
+    }
+}
+
+fn board(grid: Vec<Vec<CellContent>>, hud: Hud) -> Board {
+    Board {
+        grid,
+        hud,
+        effects: Vec::new(),
+        mode: GameMode::Classic,
+    }
+}
+
+fn play(view: &NarratedView<Vec<u8>>, grid: Vec<Vec<CellContent>>, hud: Hud) {
+    view.render(&RenderState::new(Screen::Playing(board(grid, hud))));
+}
+
+fn lines(view: NarratedView<Vec<u8>>) -> Vec<String> {
+    String::from_utf8(view.into_inner())
+        .unwrap()
+        .split_terminator("\r\n")
+        .map(String::from)
+        .collect()
+}
+
+fn going_up(y: usize) -> Vec<Vec<CellContent>> {
+    grid(&[
+        ((2, y), CellContent::Head(Orientation::Up)),
+        ((2, y + 1), BODY),
+        ((2, y + 2), CellContent::Tail(Orientation::Up)),
+    ])
+}
+
+#[test]
+fn test_describe_first_frame() {
+    let view = NarratedView::with_writer(Vec::new());
+    play(&view, going_up(2), hud(3, 0));
+    assert_eq!(
+        lines(view),
+        ["heading up at column 3 row 3. apple 2 left 2 up. \
+          up wall in 3, right wall in 3, down body in 1, left wall in 3."]
+    );
+}
+
+#[test]
+fn test_only_announce_changes() {
+    let view = NarratedView::with_writer(Vec::new());
+    play(&view, going_up(2), hud(3, 0));
+    //the same frame drawn again by an animation
+    play(&view, going_up(2), hud(3, 0));
+    //a step with nothing new ahead
+    play(&view, going_up(2), hud(3, 1));
+    let turned = grid(&[
+        ((3, 2), CellContent::Head(Orientation::Right)),
+        ((2, 2), BODY),
+        ((2, 3), CellContent::Tail(Orientation::Up)),
+    ]);
+    play(&view, turned, hud(3, 2));
+
+    let lines = lines(view);
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].starts_with("turned right. heading right at column 4 row 3."));
+    assert!(lines[1].ends_with("right wall in 2, down wall in 3, left body in 1."));
+}
+
+#[test]
+fn test_announce_danger_and_apples() {
+    let view = NarratedView::with_writer(Vec::new());
+    play(&view, going_up(2), hud(3, 0));
+    play(&view, going_up(1), hud(3, 1));
+    play(&view, going_up(0), hud(4, 2));
+
+    let lines = lines(view);
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].starts_with("danger, wall ahead in 2. "));
+    assert!(lines[2].starts_with("apple eaten, length 4. "));
+}
+
+#[test]
+fn test_no_walls_in_wrap_mode() {
+    let view = NarratedView::with_writer(Vec::new());
+    for (tick, y) in [2, 1, 0].into_iter().enumerate() {
+        let board = Board {
+            mode: GameMode::Wrap,
+            ..board(going_up(y), hud(3, tick as u64))
+        };
+        view.render(&RenderState::new(Screen::Playing(board)));
+    }
+
+    //the edges are no danger, the tail is three steps away across the top one
+    assert_eq!(
+        lines(view)[..],
+        ["heading up at column 3 row 3. apple 2 left 2 up. \
+          up tail in 3, right clear, down body in 1, left clear."]
+    );
+}
+
+#[test]
+fn test_read_menu_entries_as_they_change() {
+    let entry = |label, selected| MenuEntry {
+        label,
+        value: String::new(),
+        error: None,
+        selected,
+    };
+    let view = NarratedView::with_writer(Vec::new());
+    view.render(&RenderState::new(Screen::Menu {
+        board: board(going_up(2), hud(3, 0)),
+        title: "menu",
+        entries: vec![entry("resume", true), entry("exit", false)],
+    }));
+    view.render(&RenderState::new(Screen::Menu {
+        board: board(going_up(2), hud(3, 0)),
+        title: "menu",
+        entries: vec![entry("resume", false), entry("exit", true)],
+    }));
+    view.render(&RenderState::new(Screen::Menu {
+        board: board(going_up(2), hud(3, 0)),
+        title: "menu",
+        entries: vec![entry("resume", false), entry("exit", true)],
+    }));
+    //back in the game after the menu
+    play(&view, going_up(2), hud(3, 0));
+
+    let lines = lines(view);
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "menu. resume");
+    assert_eq!(lines[1], "exit");
+    assert!(lines[2].starts_with("heading up"));
+}
+
+#[test]
+fn test_announce_game_over_once() {
+    let view = NarratedView::with_writer(Vec::new());
+    play(&view, going_up(0), hud(3, 0));
+    let game_over = Screen::GameOver {
+        board: board(
+            going_up(0),
+            Hud {
+                score: 20,
+                apples: 2,
+                ..hud(3, 10)
+            },
+        ),
+        cause: DeathCause::Wall,
+    };
+    view.render(&RenderState::new(game_over.clone()));
+    view.render(&RenderState::new(game_over));
+
+    let lines = lines(view);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[1],
+        "game over, you hit the wall, your score is 20. length 3, 2 apples, 2 seconds. \
+         press enter to continue, esc to quit."
+    );
+}
+
+#[test]
+fn test_read_initials_as_they_are_typed() {
+    let view = NarratedView::with_writer(Vec::new());
+    let new_high_score = |initials: &str| Screen::NewHighScore {
+        board: board(
+            going_up(0),
+            Hud {
+                score: 20,
+                ..hud(3, 10)
+            },
+        ),
+        rank: 0,
+        initials: initials.to_string(),
+    };
+    for initials in ["", "", "A", "AB"] {
+        view.render(&RenderState::new(new_high_score(initials)));
+    }
+
+    let lines = lines(view);
+    assert_eq!(
+        lines,
+        [
+            "new high score, your score is: 20, number 1 of the best scores. \
+             type them and press enter, esc to skip.",
+            "your initials: A.",
+            "your initials: AB.",
+        ]
+    );
+}
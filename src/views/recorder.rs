@@ -0,0 +1,175 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::terminal;
+use serde::Serialize;
+
+//the size players assume when the terminal does not report one
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+//the first line of an asciicast v2 file
+#[derive(Serialize)]
+struct Header {
+    version: u8,
+    width: u16,
+    height: u16,
+    timestamp: u64,
+    env: BTreeMap<&'static str, String>,
+}
+
+struct Recording {
+    path: PathBuf,
+    file: BufWriter<File>,
+    started: Instant,
+    //what was written since the last flush
+    pending: Vec<u8>,
+}
+
+impl Recording {
+    fn create(path: PathBuf, (width, height): (u16, u16)) -> io::Result<Self> {
+        let mut file = BufWriter::new(File::create(&path)?);
+        let header = Header {
+            version: 2,
+            width,
+            height,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+            env: ["TERM", "SHELL"]
+                .into_iter()
+                .filter_map(|name| Some((name, env::var(name).ok()?)))
+                .collect(),
+        };
+        writeln!(file, "{}", serde_json::to_string(&header)?)?;
+        file.flush()?;
+        Ok(Recording {
+            path,
+            file,
+            started: Instant::now(),
+            pending: Vec::new(),
+        })
+    }
+
+    //a single output event for everything written since the last flush,
+    //so a character is never split between two events
+    fn flush(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        //to the microsecond, like asciinema itself
+        let time = (self.started.elapsed().as_secs_f64() * 1e6).round() / 1e6;
+        let data = String::from_utf8_lossy(&self.pending);
+        writeln!(self.file, "{}", serde_json::to_string(&(time, "o", data))?)?;
+        self.pending.clear();
+        self.file.flush()
+    }
+}
+
+//records what the terminal view writes as an asciicast v2 file, with the
+//escape sequences exactly as they were sent, to be replayed with asciinema;
+//clones share the recording, so it can be toggled while the view writes
+#[derive(Clone, Default)]
+pub struct Recorder {
+    recording: Arc<Mutex<Option<Recording>>>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Recorder::default()
+    }
+
+    //where a recording started while playing is saved, in the current directory
+    pub fn timestamped_path() -> PathBuf {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        PathBuf::from(format!("rusty-rattle-{secs}.cast"))
+    }
+
+    //saves the recording already running, if there is one, before starting the new one
+    pub fn start(&self, path: PathBuf) -> io::Result<()> {
+        let size = terminal::size()
+            .ok()
+            .filter(|(width, height)| *width > 0 && *height > 0)
+            .unwrap_or(DEFAULT_SIZE);
+        self.start_with_size(path, size)
+    }
+
+    fn start_with_size(&self, path: PathBuf, size: (u16, u16)) -> io::Result<()> {
+        let recording = Recording::create(path, size)?;
+        match self.lock().replace(recording) {
+            Some(mut previous) => previous.flush(),
+            None => Ok(()),
+        }
+    }
+
+    //returns where the recording was saved, if one was running
+    pub fn stop(&self) -> io::Result<Option<PathBuf>> {
+        let Some(mut recording) = self.lock().take() else {
+            return Ok(None);
+        };
+        recording.flush()?;
+        Ok(Some(recording.path))
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.lock().is_some()
+    }
+
+    //a writer sending everything to out, and to the recording while there is one
+    pub fn writer<W: Write>(&self, out: W) -> RecordingWriter<W> {
+        RecordingWriter {
+            out,
+            recorder: self.clone(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<Recording>> {
+        self.recording.lock().unwrap()
+    }
+}
+
+pub struct RecordingWriter<W: Write> {
+    out: W,
+    recorder: Recorder,
+}
+
+impl<W: Write> RecordingWriter<W> {
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> Write for RecordingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.out.write(buf)?;
+        if let Some(recording) = self.recorder.lock().as_mut() {
+            recording.pending.extend_from_slice(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    //the terminal view flushes once it is done with a screen,
+    //which is when it shows up in the recording too
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()?;
+        let mut recording = self.recorder.lock();
+        if let Some(Err(e)) = recording.as_mut().map(Recording::flush) {
+            //a full disk should not stop the game
+            if let Some(recording) = recording.take() {
+                eprintln!("stopped recording to {}: {e}\r", recording.path.display());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,102 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process,
+};
+
+use serde_json::Value;
+
+use super::Recorder;
+use crate::{
+    model::{
+        render_state::{Board, RenderState, Screen},
+        CellContent, DeathCause, Hud,
+    },
+    settings::GameMode,
+    views::{tui_view::TuiView, Theme, View},
+};
+
+fn cast_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("rusty-rattle-{name}-{}.cast", process::id()))
+}
+
+fn board() -> Board {
+    Board {
+        grid: vec![vec![CellContent::Empty; 6]; 4],
+        hud: Hud {
+            score: 10,
+            ..Hud::default()
+        },
+        effects: Vec::new(),
+        mode: GameMode::Classic,
+    }
+}
+
+fn read_cast(path: &Path) -> Vec<Value> {
+    let cast = fs::read_to_string(path).unwrap();
+    fs::remove_file(path).unwrap();
+    cast.lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_record_exactly_what_the_view_writes() {
+    let path = cast_path("record");
+    let recorder = Recorder::new();
+    recorder.start_with_size(path.clone(), (100, 30)).unwrap();
+
+    let view = TuiView::with_writer(recorder.writer(Vec::new()));
+    view.set_theme(&Theme::default());
+    view.render(&RenderState::new(Screen::GameOver {
+        board: board(),
+        cause: DeathCause::Wall,
+    }));
+    assert_eq!(recorder.stop().unwrap(), Some(path.clone()));
+    //once stopped, the output only goes to the terminal
+    view.render(&RenderState::new(Screen::Paused(board())));
+    let written = String::from_utf8(view.into_inner().into_inner()).unwrap();
+
+    let cast = read_cast(&path);
+    assert_eq!(cast[0]["version"], 2);
+    assert_eq!(cast[0]["width"], 100);
+    assert_eq!(cast[0]["height"], 30);
+    let recorded: String = cast[1..]
+        .iter()
+        .map(|event| {
+            assert_eq!(event[1], "o");
+            event[2].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert!(recorded.contains("GAME OVER"));
+    assert!(written.starts_with(&recorded));
+    assert!(written.len() > recorded.len());
+}
+
+#[test]
+fn test_one_event_per_flush() {
+    let path = cast_path("flush");
+    let recorder = Recorder::new();
+    let mut writer = recorder.writer(Vec::new());
+    write!(writer, "before").unwrap();
+    writer.flush().unwrap();
+
+    recorder.start_with_size(path.clone(), (80, 24)).unwrap();
+    //a character split between two writes
+    writer.write_all(&"┌".as_bytes()[..1]).unwrap();
+    writer.write_all(&"┌".as_bytes()[1..]).unwrap();
+    writer.flush().unwrap();
+    //nothing written since the last flush
+    writer.flush().unwrap();
+    write!(writer, "\x1b[2J").unwrap();
+    writer.flush().unwrap();
+    recorder.stop().unwrap();
+
+    let cast = read_cast(&path);
+    assert_eq!(cast.len(), 3);
+    assert_eq!(cast[1][2], "┌");
+    assert_eq!(cast[2][2], "\x1b[2J");
+    assert!(cast[1][0].as_f64().unwrap() <= cast[2][0].as_f64().unwrap());
+    assert!(!recorder.is_recording());
+}
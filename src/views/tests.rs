@@ -0,0 +1,53 @@
+use std::ffi::OsString;
+
+use super::{tui_view, tui_view::TuiView, ColorSupport, Theme, View};
+use crate::{
+    model::{
+        render_state::{Board, RenderState, Screen},
+        CellContent, Orientation,
+    },
+    settings::GameMode,
+    Config,
+};
+
+//truecolor, 256 colors or one of the 16 basic ones
+fn has_colors(output: &str) -> bool {
+    output.contains("\x1b[38;")
+        || (30..38)
+            .chain(90..98)
+            .any(|code| output.contains(&format!("\x1b[{code}m")))
+}
+
+#[test]
+fn test_terminal_view_follows_no_color() {
+    let mut grid = vec![vec![CellContent::Empty; 8]; 8];
+    grid[2][2] = CellContent::Head(Orientation::Up);
+    grid[3][2] = CellContent::Tail(Orientation::Up);
+    grid[5][5] = CellContent::Apple;
+    let state = RenderState::new(Screen::Playing(Board {
+        grid,
+        hud: Default::default(),
+        effects: Vec::new(),
+        mode: GameMode::Classic,
+    }));
+
+    //NO_COLOR wins over a terminal that shows any color
+    let color_support = ColorSupport::from_env(
+        Some(OsString::from("1")),
+        Some(OsString::from("truecolor")),
+        Some(OsString::from("xterm-256color")),
+        None,
+    );
+    let view = tui_view(&Config::builder().build(), color_support, Vec::new());
+    view.set_theme(&Theme::named("color").unwrap().unwrap());
+    view.render(&state);
+    let output = String::from_utf8(view.into_inner()).unwrap();
+    assert!(output.contains("\x1b[2J"));
+    assert!(!has_colors(&output));
+
+    //the same board is drawn in color when the terminal shows it
+    let view = TuiView::with_writer(Vec::new());
+    view.set_theme(&Theme::named("color").unwrap().unwrap());
+    view.render(&state);
+    assert!(has_colors(&String::from_utf8(view.into_inner()).unwrap()));
+}
@@ -0,0 +1,435 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const THEMES_DIR: &str = "themes";
+pub const RESET: &str = "\x1b[0m";
+
+//the 16 colors as xterm draws them, used to show the basic colors as rgb
+//and to find the closest basic color to any other color
+const ANSI16_RGB: [[u8; 3]; 16] = [
+    [0, 0, 0],
+    [205, 0, 0],
+    [0, 205, 0],
+    [205, 205, 0],
+    [0, 0, 238],
+    [205, 0, 205],
+    [0, 205, 205],
+    [229, 229, 229],
+    [127, 127, 127],
+    [255, 0, 0],
+    [0, 255, 0],
+    [255, 255, 0],
+    [92, 92, 255],
+    [255, 0, 255],
+    [0, 255, 255],
+    [255, 255, 255],
+];
+
+const ANSI16_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright-black",
+    "bright-red",
+    "bright-green",
+    "bright-yellow",
+    "bright-blue",
+    "bright-magenta",
+    "bright-cyan",
+    "bright-white",
+];
+
+//the levels of each channel in the 6x6x6 cube of the 256 color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Color {
+    Rgb([u8; 3]),
+    //an index in the 256 color palette
+    Ansi256(u8),
+    //an index in the 16 basic colors, the first 8 are the normal ones and the rest the bright ones
+    Ansi16(u8),
+}
+
+impl Color {
+    //"#rrggbb", the name of one of the 16 basic colors, or an index in the 256 color palette
+    pub fn parse(s: &str) -> Option<Color> {
+        let s = s.trim();
+        if let Some(hex) = s.strip_prefix('#') {
+            if hex.len() != 6 || !hex.is_ascii() {
+                return None;
+            }
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+            return Some(Color::Rgb([channel(0)?, channel(2)?, channel(4)?]));
+        }
+        if let Ok(index) = s.parse::<u8>() {
+            return Some(Color::Ansi256(index));
+        }
+        let name = s.to_lowercase().replace('_', "-");
+        ANSI16_NAMES
+            .iter()
+            .position(|n| *n == name)
+            .map(|index| Color::Ansi16(index as u8))
+    }
+
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::Rgb(rgb) => rgb,
+            Color::Ansi16(index) => ANSI16_RGB[index as usize % 16],
+            Color::Ansi256(index @ 0..=15) => ANSI16_RGB[index as usize],
+            Color::Ansi256(index @ 16..=231) => {
+                let cube = index - 16;
+                [
+                    CUBE_LEVELS[(cube / 36) as usize],
+                    CUBE_LEVELS[(cube / 6 % 6) as usize],
+                    CUBE_LEVELS[(cube % 6) as usize],
+                ]
+            }
+            Color::Ansi256(index) => {
+                let gray = 8 + 10 * (index - 232);
+                [gray; 3]
+            }
+        }
+    }
+
+    fn to_ansi256(self) -> u8 {
+        match self {
+            Color::Ansi256(index) => index,
+            Color::Ansi16(index) => index % 16,
+            //the first 16 colors are left out, terminals often change them
+            Color::Rgb(rgb) => closest(rgb, (16..=255).map(|i| (i, Color::Ansi256(i).rgb()))),
+        }
+    }
+
+    fn to_ansi16(self) -> u8 {
+        match self {
+            Color::Ansi16(index) => index % 16,
+            Color::Ansi256(index @ 0..=15) => index,
+            _ => closest(self.rgb(), (0..16).map(|i| (i, ANSI16_RGB[i as usize]))),
+        }
+    }
+
+    //the escape code setting the foreground to this color, as close as the terminal allows
+    pub fn escape(self, support: ColorSupport) -> Option<String> {
+        self.sgr(support, false)
+    }
+
+    pub fn background_escape(self, support: ColorSupport) -> Option<String> {
+        self.sgr(support, true)
+    }
+
+    fn sgr(self, support: ColorSupport, background: bool) -> Option<String> {
+        //the codes for the background are the ones of the foreground plus 10
+        let layer = if background { 10 } else { 0 };
+        match (support, self) {
+            (ColorSupport::None, _) => None,
+            (ColorSupport::TrueColor, Color::Rgb([r, g, b])) => {
+                Some(format!("\x1b[{};2;{r};{g};{b}m", 38 + layer))
+            }
+            (
+                ColorSupport::TrueColor | ColorSupport::Ansi256,
+                Color::Rgb(_) | Color::Ansi256(_),
+            ) => Some(format!("\x1b[{};5;{}m", 38 + layer, self.to_ansi256())),
+            _ => match self.to_ansi16() {
+                index @ 0..=7 => Some(format!("\x1b[{}m", 30 + layer + index)),
+                index => Some(format!("\x1b[{}m", 90 + layer + index - 8)),
+            },
+        }
+    }
+}
+
+fn closest(rgb: [u8; 3], candidates: impl Iterator<Item = (u8, [u8; 3])>) -> u8 {
+    let distance = |other: [u8; 3]| {
+        rgb.iter()
+            .zip(other)
+            .map(|(a, b)| (*a as i32 - b as i32).pow(2))
+            .sum::<i32>()
+    };
+    candidates
+        .min_by_key(|(_, other)| distance(*other))
+        .map_or(0, |(index, _)| index)
+}
+
+//how many colors the terminal can show
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorSupport {
+    None,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorSupport {
+    pub fn detect() -> Self {
+        Self::from_env(
+            env::var_os("NO_COLOR"),
+            env::var_os("COLORTERM"),
+            env::var_os("TERM"),
+            env::var_os("WT_SESSION"),
+        )
+    }
+
+    //NO_COLOR turns colors off whenever it is set to anything (https://no-color.org)
+    pub fn from_env(
+        no_color: Option<OsString>,
+        colorterm: Option<OsString>,
+        term: Option<OsString>,
+        wt_session: Option<OsString>,
+    ) -> Self {
+        if no_color.is_some_and(|value| !value.is_empty()) {
+            return ColorSupport::None;
+        }
+        let colorterm = colorterm
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorSupport::TrueColor;
+        }
+        let term = term.unwrap_or_default().to_string_lossy().to_lowercase();
+        if term == "dumb" {
+            ColorSupport::None
+        } else if term.contains("truecolor") || term.contains("direct") {
+            ColorSupport::TrueColor
+        } else if term.contains("256color") {
+            ColorSupport::Ansi256
+        } else if term.is_empty() && wt_session.is_some() {
+            //windows terminal does not set TERM, but it shows any color
+            ColorSupport::TrueColor
+        } else {
+            ColorSupport::Ansi16
+        }
+    }
+}
+
+//what each color of a theme is used for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Head,
+    Body,
+    Tail,
+    Apple,
+    Border,
+    Text,
+}
+
+impl Role {
+    pub const ALL: [Role; 6] = [
+        Role::Head,
+        Role::Body,
+        Role::Tail,
+        Role::Apple,
+        Role::Border,
+        Role::Text,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Role::Head => "head",
+            Role::Body => "body",
+            Role::Tail => "tail",
+            Role::Apple => "apple",
+            Role::Border => "border",
+            Role::Text => "text",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Role> {
+        Role::ALL.into_iter().find(|role| role.name() == name)
+    }
+}
+
+//the colors the game is drawn with, either one of the presets or a file
+//in the themes directory, remembered in the settings by its name
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct Theme {
+    name: String,
+    //roles without a color use the terminal's own
+    colors: BTreeMap<Role, Color>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::presets().swap_remove(0)
+    }
+}
+
+impl From<Theme> for String {
+    fn from(theme: Theme) -> Self {
+        theme.name
+    }
+}
+
+//themes that are not there anymore fall back to the default one
+impl From<String> for Theme {
+    fn from(name: String) -> Self {
+        Theme::named(&name)
+            .unwrap_or_else(|e| {
+                eprintln!("{e}\r");
+                None
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Theme {
+    pub fn presets() -> Vec<Theme> {
+        let preset = |name: &str, colors: &[(Role, Color)]| Theme {
+            name: name.to_string(),
+            colors: colors.iter().copied().collect(),
+        };
+        vec![
+            preset(
+                "color",
+                &[
+                    (Role::Head, Color::Ansi16(2)),
+                    (Role::Body, Color::Ansi16(2)),
+                    (Role::Tail, Color::Ansi16(2)),
+                    (Role::Apple, Color::Ansi16(1)),
+                    (Role::Border, Color::Ansi16(4)),
+                ],
+            ),
+            preset("monochrome", &[]),
+            //blue and orange from the Okabe-Ito palette stay apart with every kind of color blindness
+            preset(
+                "colorblind",
+                &[
+                    (Role::Head, Color::Rgb([0x56, 0xb4, 0xe9])),
+                    (Role::Body, Color::Rgb([0x00, 0x72, 0xb2])),
+                    (Role::Tail, Color::Rgb([0x00, 0x72, 0xb2])),
+                    (Role::Apple, Color::Rgb([0xe6, 0x9f, 0x00])),
+                    (Role::Border, Color::Rgb([0x99, 0x99, 0x99])),
+                ],
+            ),
+            preset(
+                "high-contrast",
+                &[
+                    (Role::Head, Color::Ansi16(15)),
+                    (Role::Body, Color::Ansi16(11)),
+                    (Role::Tail, Color::Ansi16(11)),
+                    (Role::Apple, Color::Ansi16(9)),
+                    (Role::Border, Color::Ansi16(15)),
+                    (Role::Text, Color::Ansi16(15)),
+                ],
+            ),
+        ]
+    }
+
+    //the presets followed by the theme files, sorted by name,
+    //files with the name of a preset or that fail to parse are left out
+    pub fn available() -> Vec<Theme> {
+        let mut themes = Theme::presets();
+        let mut files: Vec<PathBuf> = Self::themes_dir()
+            .and_then(|dir| fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "toml")
+            })
+            .collect();
+        files.sort();
+        for path in files {
+            match Theme::load_file(&path) {
+                Ok(theme) if themes.iter().all(|other| other.name != theme.name) => {
+                    themes.push(theme)
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("skipping theme {}: {e}\r", path.display()),
+            }
+        }
+        themes
+    }
+
+    //a preset or a file of the themes directory, None if there is no such theme,
+    //an error for a file that can't be read or a name reaching outside of the directory
+    pub fn named(name: &str) -> Result<Option<Theme>, String> {
+        Self::named_in(Self::themes_dir(), name)
+    }
+
+    fn named_in(themes_dir: Option<PathBuf>, name: &str) -> Result<Option<Theme>, String> {
+        if let Some(theme) = Theme::presets()
+            .into_iter()
+            .find(|theme| theme.name == name)
+        {
+            return Ok(Some(theme));
+        }
+        if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+            return Err(format!("invalid theme name {name:?}"));
+        }
+        let Some(dir) = themes_dir else {
+            return Ok(None);
+        };
+        let path = dir.join(format!("{name}.toml"));
+        match Theme::load_file(&path) {
+            Err(_) if !path.exists() => Ok(None),
+            result => result
+                .map(Some)
+                .map_err(|e| format!("invalid theme {}: {e}", path.display())),
+        }
+    }
+
+    pub fn load_file(path: &Path) -> Result<Theme, String> {
+        let name = path
+            .file_stem()
+            .ok_or_else(|| "not a file".to_string())?
+            .to_string_lossy();
+        let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Theme::parse(&name, &contents)
+    }
+
+    //a table of roles to colors, missing roles use the terminal's own color:
+    //  head = "#56b4e9"
+    //  body = 33
+    //  apple = "bright-red"
+    pub fn parse(name: &str, contents: &str) -> Result<Theme, String> {
+        let table: BTreeMap<String, toml::Value> =
+            toml::from_str(contents).map_err(|e| e.message().to_string())?;
+        let mut colors = BTreeMap::new();
+        for (key, value) in table {
+            let role = Role::from_name(&key).ok_or_else(|| format!("unknown color {key}"))?;
+            let color = match &value {
+                toml::Value::String(s) => Color::parse(s),
+                toml::Value::Integer(index) => u8::try_from(*index).ok().map(Color::Ansi256),
+                _ => None,
+            }
+            .ok_or_else(|| format!("invalid color for {key}: {value}"))?;
+            colors.insert(role, color);
+        }
+        Ok(Theme {
+            name: name.to_string(),
+            colors,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn color(&self, role: Role) -> Option<Color> {
+        self.colors.get(&role).copied()
+    }
+
+    fn themes_dir() -> Option<PathBuf> {
+        Some(storage::config_dir()?.join(THEMES_DIR))
+    }
+}
+
+#[cfg(test)]
+mod tests;
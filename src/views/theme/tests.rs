@@ -0,0 +1,142 @@
+use std::{env, ffi::OsString, fs, process};
+
+use super::{Color, ColorSupport, Role, Theme};
+
+#[test]
+fn test_parse_color() {
+    assert_eq!(Color::parse("#ff8000"), Some(Color::Rgb([255, 128, 0])));
+    assert_eq!(Color::parse("33"), Some(Color::Ansi256(33)));
+    assert_eq!(Color::parse("Bright_Red"), Some(Color::Ansi16(9)));
+    assert_eq!(Color::parse("#12345"), None);
+    assert_eq!(Color::parse("256"), None);
+    assert_eq!(Color::parse("purple"), None);
+}
+
+#[test]
+fn test_color_fallback() {
+    let red = Color::Rgb([255, 0, 0]);
+    assert_eq!(
+        red.escape(ColorSupport::TrueColor).as_deref(),
+        Some("\x1b[38;2;255;0;0m")
+    );
+    assert_eq!(
+        red.escape(ColorSupport::Ansi256).as_deref(),
+        Some("\x1b[38;5;196m")
+    );
+    assert_eq!(
+        red.escape(ColorSupport::Ansi16).as_deref(),
+        Some("\x1b[91m")
+    );
+    assert_eq!(red.escape(ColorSupport::None), None);
+
+    //colors are never shown with more colors than they were given with
+    assert_eq!(
+        Color::Ansi16(2).escape(ColorSupport::TrueColor).as_deref(),
+        Some("\x1b[32m")
+    );
+    assert_eq!(
+        Color::Ansi256(244)
+            .escape(ColorSupport::TrueColor)
+            .as_deref(),
+        Some("\x1b[38;5;244m")
+    );
+    assert_eq!(Color::Ansi256(244).rgb(), [128, 128, 128]);
+    assert_eq!(
+        Color::Ansi256(244).escape(ColorSupport::Ansi16).as_deref(),
+        Some("\x1b[90m")
+    );
+}
+
+#[test]
+fn test_color_support_from_env() {
+    let var = |value: &str| Some(OsString::from(value));
+    assert_eq!(
+        ColorSupport::from_env(var("1"), var("truecolor"), var("xterm-256color"), None),
+        ColorSupport::None
+    );
+    assert_eq!(
+        ColorSupport::from_env(var(""), var("truecolor"), var("xterm"), None),
+        ColorSupport::TrueColor
+    );
+    assert_eq!(
+        ColorSupport::from_env(None, None, var("xterm-256color"), None),
+        ColorSupport::Ansi256
+    );
+    assert_eq!(
+        ColorSupport::from_env(None, None, var("xterm"), None),
+        ColorSupport::Ansi16
+    );
+    assert_eq!(
+        ColorSupport::from_env(None, None, var("dumb"), None),
+        ColorSupport::None
+    );
+    //windows terminal does not set TERM
+    assert_eq!(
+        ColorSupport::from_env(None, None, None, var("{1234}")),
+        ColorSupport::TrueColor
+    );
+    assert_eq!(
+        ColorSupport::from_env(None, None, None, None),
+        ColorSupport::Ansi16
+    );
+}
+
+#[test]
+fn test_parse_theme() {
+    let theme = Theme::parse(
+        "mine",
+        "head = \"#56b4e9\"\nbody = 33\napple = \"bright-red\"\n",
+    )
+    .unwrap();
+    assert_eq!(theme.name(), "mine");
+    assert_eq!(
+        theme.color(Role::Head),
+        Some(Color::Rgb([0x56, 0xb4, 0xe9]))
+    );
+    assert_eq!(theme.color(Role::Body), Some(Color::Ansi256(33)));
+    assert_eq!(theme.color(Role::Apple), Some(Color::Ansi16(9)));
+    assert_eq!(theme.color(Role::Tail), None);
+
+    assert!(Theme::parse("bad", "snake = \"red\"").is_err());
+    assert!(Theme::parse("bad", "head = \"purple\"").is_err());
+    assert!(Theme::parse("bad", "head = 300").is_err());
+}
+
+#[test]
+fn test_theme_is_saved_by_name() {
+    assert_eq!(
+        serde_json::to_string(&Theme::default()).unwrap(),
+        "\"color\""
+    );
+    let theme: Theme = serde_json::from_str("\"high-contrast\"").unwrap();
+    assert_eq!(theme.name(), "high-contrast");
+    assert_eq!(theme.color(Role::Apple), Some(Color::Ansi16(9)));
+
+    //themes that cannot be found anymore fall back to the default one
+    let theme: Theme = serde_json::from_str("\"not-a-preset\"").unwrap();
+    assert_eq!(theme, Theme::default());
+}
+
+#[test]
+fn test_theme_files_by_name() {
+    let dir = env::temp_dir().join(format!("rusty-rattle-themes-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("mine.toml"), "head = 33\n").unwrap();
+    fs::write(dir.join("broken.toml"), "head = \"pink\"\n").unwrap();
+    let named = |name| Theme::named_in(Some(dir.clone()), name);
+
+    assert_eq!(
+        named("mine").unwrap().unwrap().color(Role::Head),
+        Some(Color::Ansi256(33))
+    );
+    assert_eq!(named("missing"), Ok(None));
+    //a file that is there is reported with what is wrong with it
+    assert!(named("broken")
+        .unwrap_err()
+        .contains("invalid color for head"));
+    //names never reach outside of the themes directory
+    for name in ["../mine", "../../etc/x", "a/b", "a\\b", ""] {
+        assert!(named(name).unwrap_err().contains("invalid theme name"));
+    }
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,44 @@
+use crate::model::{CellContent, Orientation};
+
+//the shapes a cell is drawn with by the graphical views,
+//following the unicode glyph set of the text view
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TileShape {
+    //a line from the center of the cell to the middle of one of its edges
+    Stroke(Orientation),
+    //a triangle with its base on the opposite edge, pointing towards the orientation
+    Arrow(Orientation),
+    //a circle in the middle of the cell
+    Dot,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TileColor {
+    Head,
+    Body,
+    Tail,
+    Apple,
+}
+
+pub fn tile(cell: &CellContent) -> Option<(TileColor, Vec<TileShape>)> {
+    match cell {
+        CellContent::Empty => None,
+        CellContent::Head(orientation) => {
+            Some((TileColor::Head, vec![TileShape::Arrow(*orientation)]))
+        }
+        //the tail points towards the rest of the body
+        CellContent::Tail(orientation) => {
+            Some((TileColor::Tail, vec![TileShape::Stroke(*orientation)]))
+        }
+        //the snake comes in from the edge opposite to where it was going
+        //and leaves from the edge it is going towards
+        CellContent::Body { towards, from } => Some((
+            TileColor::Body,
+            vec![
+                TileShape::Stroke(from.opposite()),
+                TileShape::Stroke(*towards),
+            ],
+        )),
+        CellContent::Apple => Some((TileColor::Apple, vec![TileShape::Dot])),
+    }
+}
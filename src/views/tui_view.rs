@@ -1,206 +1,884 @@
-use std::io::{self, Write};
+use std::{
+    cell::{Cell, RefCell},
+    io::{self, Stdout, Write},
+    str::FromStr,
+};
 
-use crate::model::{CellContent, Orientation};
+use crate::{
+    high_scores::{HighScore, INITIALS_LEN},
+    locale::{self, Locale, Messages},
+    model::{
+        animation::Effect,
+        render_state::{Board, RenderState, Screen},
+        title_menu::MenuEntry,
+        CellContent, DeathCause, Hud,
+    },
+    stats::Stats,
+};
 
-use super::View;
+use camera::Camera;
+use crossterm::terminal;
 
-// linux colors
-#[cfg(any(target_os = "linux", target_os = "macos"))]
-const WHITE: &str = "\u{1b}[0m";
-#[cfg(any(target_os = "linux", target_os = "macos"))]
-const GREEN: &str = "\u{1b}[32m";
-#[cfg(any(target_os = "linux", target_os = "macos"))]
-const RED: &str = "\u{1b}[31m";
-#[cfg(any(target_os = "linux", target_os = "macos"))]
-const BLUE: &str = "\u{1b}[34m";
+use super::{
+    glyphs::GlyphSet,
+    theme::{ColorSupport, Role, RESET},
+    Theme, View,
+};
 
-// windows colors
-#[cfg(target_os = "windows")]
-const WHITE: &str = "[0m";
-#[cfg(target_os = "windows")]
-const GREEN: &str = "[32m";
-#[cfg(target_os = "windows")]
-const RED: &str = "[31m";
-#[cfg(target_os = "windows")]
-const BLUE: &str = "[34m";
+mod camera;
+mod dense;
 
-// default colors
-#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
-const WHITE: &str = "";
-#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
-const GREEN: &str = "";
-#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
-const RED: &str = "";
-#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
-const BLUE: &str = "";
+//the most braille characters the minimap takes, in columns and rows
+const MINIMAP_SIZE: (usize, usize) = (16, 4);
 
-#[derive(Default)]
-pub struct TuiView {}
+//the name of the game, drawn the same in every language
+const BANNER: [&str; 5] = [
+    "██████  ██    ██ ███████ ████████ ██    ██     ██████   █████  ████████ ████████ ██      ███████ ",
+    "██   ██ ██    ██ ██         ██     ██  ██      ██   ██ ██   ██    ██       ██    ██      ██      ",
+    "██████  ██    ██ ███████    ██      ████       ██████  ███████    ██       ██    ██      █████   ",
+    "██   ██ ██    ██      ██    ██       ██        ██   ██ ██   ██    ██       ██    ██      ██      ",
+    "██   ██  ██████  ███████    ██       ██        ██   ██ ██   ██    ██       ██    ███████ ███████ ",
+];
 
-impl View for TuiView {
-    fn draw_title_screen(&self) {
-        Self::draw_title_screen();
+//how big the screen the view draws on is
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScreenSize {
+    //the size of the terminal, asked again for every frame
+    Terminal,
+    //columns and rows
+    Fixed(usize, usize),
+    //the whole board is always drawn
+    Unbounded,
+}
+
+//the sides of the board hidden beyond the edges of the screen
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Cut {
+    top: bool,
+    bottom: bool,
+    left: bool,
+    right: bool,
+}
+
+//how many cells of the board go in a character of the terminal
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RenderMode {
+    //every cell takes two columns, drawn with the glyph set
+    #[default]
+    Cells,
+    //two cells on top of each other in a character, with the upper and lower half blocks
+    HalfBlock,
+    //2x4 cells in a character, as the dots of a braille pattern
+    Braille,
+}
+
+impl RenderMode {
+    pub const ALL: [RenderMode; 3] = [
+        RenderMode::Cells,
+        RenderMode::HalfBlock,
+        RenderMode::Braille,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RenderMode::Cells => "cells",
+            RenderMode::HalfBlock => "half-block",
+            RenderMode::Braille => "braille",
+        }
+    }
+
+    //the columns and rows a board of (width, height) cells takes, walls included
+    fn board_extent(&self, (width, height): (usize, usize)) -> (usize, usize) {
+        match self {
+            RenderMode::Cells => (width * 2 + 4, height + 2),
+            RenderMode::HalfBlock => (width + 2, height.div_ceil(2) + 2),
+            RenderMode::Braille => (width.div_ceil(2) + 2, height.div_ceil(4) + 2),
+        }
+    }
+
+    //the cells that fit in a screen of (columns, rows), leaving room for the walls,
+    //the hud and a message under it
+    fn visible_cells(&self, (columns, rows): (usize, usize)) -> (usize, usize) {
+        let rows = rows.saturating_sub(5);
+        match self {
+            RenderMode::Cells => (columns.saturating_sub(4) / 2, rows),
+            RenderMode::HalfBlock => (columns.saturating_sub(2), rows * 2),
+            RenderMode::Braille => (columns.saturating_sub(2) * 2, rows * 4),
+        }
+    }
+}
+
+impl FromStr for RenderMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RenderMode::ALL
+            .into_iter()
+            .find(|mode| mode.name() == s.to_lowercase())
+            .ok_or_else(|| {
+                format!("unknown render mode {s:?}, expected cells, half-block or braille")
+            })
+    }
+}
+
+//draws the game as text and ANSI escape codes into a writer,
+//the terminal by default, or a buffer, a file or a socket
+pub struct TuiView<W: Write = Stdout> {
+    out: RefCell<W>,
+    theme: RefCell<Theme>,
+    locale: Cell<Locale>,
+    color_support: ColorSupport,
+    glyphs: GlyphSet,
+    render_mode: RenderMode,
+    screen_size: ScreenSize,
+    camera: RefCell<Camera>,
+    minimap: bool,
+}
+
+impl Default for TuiView {
+    fn default() -> Self {
+        TuiView::with_writer(io::stdout())
     }
+}
 
-    fn draw_frame(&self, grid: Vec<Vec<CellContent>>) {
-        Self::draw_grid(grid);
+impl<W: Write + Send> View for TuiView<W> {
+    fn set_theme(&self, theme: &Theme) {
+        *self.theme.borrow_mut() = theme.clone();
     }
 
-    fn draw_game_over(&self, score: usize) {
-        Self::draw_game_over(score)
+    fn set_locale(&self, locale: Locale) {
+        self.locale.set(locale);
+    }
+
+    fn render(&self, state: &RenderState) {
+        match &state.screen {
+            Screen::Title { menu, controls } => self.draw_title_screen(menu, controls),
+            Screen::Controls { bindings, message } => {
+                self.draw_controls(bindings, message.as_deref())
+            }
+            Screen::Playing(board) => self.draw_board(board),
+            Screen::Paused(board) => {
+                self.draw_board(board);
+                self.draw_pause();
+            }
+            Screen::Menu {
+                board,
+                title,
+                entries,
+            } => {
+                self.draw_board(board);
+                self.draw_menu_overlay(title, entries, board.size());
+            }
+            Screen::GameOver { board, cause } => {
+                self.draw_board(board);
+                self.draw_game_over(&board.hud, *cause, board.size());
+            }
+            Screen::Victory(board) => {
+                self.draw_board(board);
+                self.draw_victory(&board.hud, board.size());
+            }
+            Screen::NewHighScore {
+                board,
+                rank,
+                initials,
+            } => {
+                self.draw_board(board);
+                self.draw_new_high_score(&board.hud, *rank, initials, board.size());
+            }
+            Screen::HighScores { settings, scores } => self.draw_high_scores(settings, scores),
+            Screen::Stats(stats) => self.draw_stats(stats),
+            Screen::ResumeOffer(board) => {
+                self.draw_board(board);
+                self.draw_resume_offer(&board.hud, board.size());
+            }
+        }
+        if let Some(lines) = &state.debug {
+            self.draw_debug_overlay(lines);
+        }
     }
 }
 
 impl TuiView {
     pub fn new() -> Self {
-        crossterm::style::Colored::set_ansi_color_disabled(false);
-        TuiView {}
+        TuiView::default().with_color_support(ColorSupport::detect())
+    }
+}
+
+impl<W: Write> TuiView<W> {
+    pub fn with_writer(out: W) -> Self {
+        TuiView {
+            out: RefCell::new(out),
+            theme: RefCell::new(Theme::default()),
+            locale: Cell::new(Locale::default()),
+            color_support: ColorSupport::TrueColor,
+            glyphs: GlyphSet::default(),
+            render_mode: RenderMode::default(),
+            screen_size: ScreenSize::Unbounded,
+            camera: RefCell::new(Camera::default()),
+            minimap: false,
+        }
+    }
+
+    //boards bigger than the screen are scrolled to follow the head,
+    //the dead zones are how close to the edges it can get before that
+    pub fn with_camera(mut self, screen_size: ScreenSize, dead_zone: (usize, usize)) -> Self {
+        self.screen_size = screen_size;
+        self.camera = RefCell::new(Camera::new(dead_zone));
+        self
+    }
+
+    //an overview of the whole board, shown while it is scrolled
+    pub fn with_minimap(mut self, minimap: bool) -> Self {
+        self.minimap = minimap;
+        self
+    }
+
+    pub fn with_render_mode(mut self, render_mode: RenderMode) -> Self {
+        self.render_mode = render_mode;
+        self
+    }
+
+    pub fn with_glyphs(mut self, glyphs: GlyphSet) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    //the theme colors are approximated with the ones the output can show
+    pub fn with_color_support(mut self, color_support: ColorSupport) -> Self {
+        self.color_support = color_support;
+        self
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out.into_inner()
+    }
+
+    //the view has no way to report errors, a writer that stopped
+    //accepting output, like a disconnected client, just misses the frames
+    fn write(&self, s: &str) {
+        let _ = self.out.borrow_mut().write_all(s.as_bytes());
     }
 
-    fn draw_grid(grid: Vec<Vec<CellContent>>) {
+    fn flush(&self) {
+        let _ = self.out.borrow_mut().flush();
+    }
+
+    fn messages(&self) -> &'static Messages {
+        self.locale.get().messages()
+    }
+
+    fn draw_board(&self, board: &Board) {
+        self.draw_grid(&board.grid, &board.effects);
+        self.draw_hud(&board.hud);
+    }
+
+    fn draw_grid(&self, grid: &[Vec<CellContent>], effects: &[Effect]) {
+        let (mut visible, cut, (left, top)) = self.crop(grid);
+        //the cells of the effects that are on the screen
+        let effect_cells: Vec<(usize, usize)> = effects
+            .iter()
+            .flat_map(|effect| effect.cells(grid))
+            .filter_map(|(x, y)| Some((x.checked_sub(left)?, y.checked_sub(top)?)))
+            .filter(|&(x, y)| y < visible.len() && x < visible[y].len())
+            .collect();
+
         // clears the screen using ANSI escape codes
-        print!("\x1B[2J\x1B[1;1H");
+        self.write("\x1B[2J\x1B[1;1H");
+
+        match self.render_mode {
+            RenderMode::Cells => self.draw_cells(&visible, cut, &effect_cells),
+            //a dot in the apple color is all a dense character can show
+            RenderMode::HalfBlock | RenderMode::Braille => {
+                for &(x, y) in &effect_cells {
+                    visible[y][x] = CellContent::Apple;
+                }
+                self.draw_dense(&visible, cut);
+            }
+        }
+
+        if self.minimap && cut != Cut::default() {
+            self.draw_minimap(grid, &visible);
+        }
+
+        //flush the output buffer
+        self.flush();
+    }
+
+    fn draw_dense(&self, visible: &[Vec<CellContent>], cut: Cut) {
+        match self.render_mode {
+            RenderMode::Cells => {}
+            RenderMode::HalfBlock => {
+                let rows = dense::half_blocks(visible);
+                self.draw_dense_rows(&rows, cut, |(top, bottom)| {
+                    self.draw_half_block(*top, *bottom)
+                });
+            }
+            RenderMode::Braille => {
+                let rows = dense::braille(visible);
+                self.draw_dense_rows(&rows, cut, |(glyph, role)| self.draw_braille(*glyph, *role));
+            }
+        }
+    }
+
+    fn draw_cells(&self, grid: &[Vec<CellContent>], cut: Cut, effect_cells: &[(usize, usize)]) {
+        let border = self.glyphs.border();
+        let columns = grid.first().map_or(0, |row| row.len());
+        let wall = |open| match open {
+            true => (border.open_horizontal, border.open_vertical),
+            false => (border.horizontal, border.vertical),
+        };
 
         // top box wall
-        Self::print_blue("╔═");
-        for _ in 0..grid[0].len() {
-            Self::print_blue("══");
+        self.print(Role::Border, border.top_left);
+        for _ in 0..columns {
+            self.print(Role::Border, wall(cut.top).0);
         }
-        Self::print_blue("╗ ");
-        print!("\n\r");
+        self.print(Role::Border, border.top_right);
+        self.write("\n\r");
 
-        for row in &grid {
-            Self::print_blue("║ ");
-            for cell in row {
-                Self::draw_cell(&cell)
+        for (y, row) in grid.iter().enumerate() {
+            self.print(Role::Border, wall(cut.left).1);
+            for (x, cell) in row.iter().enumerate() {
+                if effect_cells.contains(&(x, y)) {
+                    self.print(Role::Apple, self.glyphs.pulse());
+                } else {
+                    self.draw_cell(cell)
+                }
             }
-            Self::print_blue("║ ");
-            print!("\n\r");
+            self.print(Role::Border, wall(cut.right).1);
+            self.write("\n\r");
         }
 
         // bottom box wall
-        Self::print_blue("╚═");
-        for _ in 0..grid[0].len() {
-            Self::print_blue("══");
+        self.print(Role::Border, border.bottom_left);
+        for _ in 0..columns {
+            self.print(Role::Border, wall(cut.bottom).0);
         }
-        Self::print_blue("╝ ");
-        print!("\n\r");
+        self.print(Role::Border, border.bottom_right);
+        self.write("\n\r");
+    }
 
-        //flush the output buffer
-        std::io::stdout().flush().unwrap();
+    //the dense modes need unicode anyway, so their walls are always drawn with thin lines
+    fn draw_dense_rows<T>(&self, rows: &[Vec<T>], cut: Cut, draw: impl Fn(&T)) {
+        let columns = rows.first().map_or(0, |row| row.len());
+        let wall = |open| match open {
+            true => ("┄", "┆"),
+            false => ("─", "│"),
+        };
+        self.print(
+            Role::Border,
+            &format!("┌{}┐", wall(cut.top).0.repeat(columns)),
+        );
+        self.write("\n\r");
+        for row in rows {
+            self.print(Role::Border, wall(cut.left).1);
+            row.iter().for_each(&draw);
+            self.print(Role::Border, wall(cut.right).1);
+            self.write("\n\r");
+        }
+        self.print(
+            Role::Border,
+            &format!("└{}┘", wall(cut.bottom).0.repeat(columns)),
+        );
+        self.write("\n\r");
     }
 
-    fn draw_cell(cell: &CellContent) {
-        match cell {
-            CellContent::Empty => print!("  "),
+    fn draw_braille(&self, glyph: char, role: Option<Role>) {
+        match role {
+            Some(role) => self.print(role, &glyph.to_string()),
+            None => self.write(&glyph.to_string()),
+        }
+    }
 
-            CellContent::Head(orientation) => match orientation {
-                Orientation::Up => Self::print_green("▲ "),
-                Orientation::Right => Self::print_green("▶ "),
-                Orientation::Down => Self::print_green("▼ "),
-                Orientation::Left => Self::print_green(" ◀"),
-            },
+    //the cells of the board that fit on the screen, around the head,
+    //on which sides the board goes on beyond them, and the top left visible cell
+    fn crop(&self, grid: &[Vec<CellContent>]) -> (Vec<Vec<CellContent>>, Cut, (usize, usize)) {
+        let board = (grid.first().map_or(0, |row| row.len()), grid.len());
+        let (width, height) = self.visible_size(board);
+        if (width, height) == board {
+            return (grid.to_vec(), Cut::default(), (0, 0));
+        }
+
+        let head = grid
+            .iter()
+            .enumerate()
+            .find_map(|(y, row)| {
+                row.iter()
+                    .position(|cell| matches!(cell, CellContent::Head(_)))
+                    .map(|x| (x, y))
+            })
+            .unwrap_or((board.0 / 2, board.1 / 2));
+        let (x, y) = self
+            .camera
+            .borrow_mut()
+            .follow(head, (width, height), board);
 
-            CellContent::Tail(orientation) => match orientation {
-                Orientation::Up => Self::print_green("╵ "),
-                Orientation::Right => Self::print_green(" ╶"),
-                Orientation::Down => Self::print_green("╷ "),
-                Orientation::Left => Self::print_green("╴ "),
+        let visible = grid[y..y + height]
+            .iter()
+            .map(|row| row[x..x + width].to_vec())
+            .collect();
+        let cut = Cut {
+            top: y > 0,
+            bottom: y + height < board.1,
+            left: x > 0,
+            right: x + width < board.0,
+        };
+        (visible, cut, (x, y))
+    }
+
+    //the cells of a board of the given size that fit on the screen
+    fn visible_size(&self, (width, height): (usize, usize)) -> (usize, usize) {
+        let screen = match self.screen_size {
+            ScreenSize::Unbounded => return (width, height),
+            ScreenSize::Fixed(columns, rows) => (columns, rows),
+            //some terminals, like ptys nobody set a size for, report zero
+            ScreenSize::Terminal => match terminal::size() {
+                Ok((columns, rows)) if columns > 0 && rows > 0 => (columns as usize, rows as usize),
+                _ => return (width, height),
             },
+        };
+        let (columns, rows) = self.render_mode.visible_cells(screen);
+        (width.min(columns.max(1)), height.min(rows.max(1)))
+    }
 
-            CellContent::Body { towards, from } => match (towards, from) {
-                (Orientation::Up, Orientation::Down) => {
-                    panic!("impossible snake orientation {:?}", (from, towards))
-                }
-                (Orientation::Up, Orientation::Left) => Self::print_green("┗━"),
-                (Orientation::Up, Orientation::Up) => Self::print_green("┃ "),
-                (Orientation::Up, Orientation::Right) => Self::print_green("┛ "),
-                (Orientation::Right, Orientation::Down) => Self::print_green("┗━"),
-                (Orientation::Right, Orientation::Left) => {
-                    panic!("impossible snake orientation {:?}", (from, towards))
-                }
-                (Orientation::Right, Orientation::Up) => Self::print_green("┏━"),
-                (Orientation::Right, Orientation::Right) => Self::print_green("━━"),
-                (Orientation::Down, Orientation::Down) => Self::print_green("┃ "),
-                (Orientation::Down, Orientation::Left) => Self::print_green("┏━"),
-                (Orientation::Down, Orientation::Up) => {
-                    panic!("impossible snake orientation {:?}", (from, towards))
-                }
-                (Orientation::Down, Orientation::Right) => Self::print_green("┓ "),
-                (Orientation::Left, Orientation::Down) => Self::print_green("┛ "),
-                (Orientation::Left, Orientation::Left) => Self::print_green("━━"),
-                (Orientation::Left, Orientation::Up) => Self::print_green("┓ "),
-                (Orientation::Left, Orientation::Right) => {
-                    panic!("impossible snake orientation {:?}", (from, towards))
+    //the whole board shrunk into braille dots in the top right corner,
+    //with every dot standing for a block of cells
+    fn draw_minimap(&self, grid: &[Vec<CellContent>], visible: &[Vec<CellContent>]) {
+        let board = (grid.first().map_or(0, |row| row.len()), grid.len());
+        let block = (
+            board.0.div_ceil(MINIMAP_SIZE.0 * 2),
+            board.1.div_ceil(MINIMAP_SIZE.1 * 4),
+        );
+        let shrunk: Vec<Vec<CellContent>> = grid
+            .chunks(block.1)
+            .map(|rows| {
+                (0..board.0.div_ceil(block.0))
+                    .map(|x| {
+                        let cells = rows
+                            .iter()
+                            .flat_map(|row| row.iter().skip(x * block.0).take(block.0));
+                        dense::most_visible(cells)
+                    })
+                    .collect()
+            })
+            .collect();
+        let rows = dense::braille(&shrunk);
+
+        let columns = rows.first().map_or(0, |row| row.len());
+        let visible = (visible.first().map_or(0, |row| row.len()), visible.len());
+        let (board_columns, _) = self.render_mode.board_extent(visible);
+        //inside the walls, and the trailing column of the walls on the right
+        if board_columns < columns + 6 {
+            return;
+        }
+        let column = board_columns - columns - 3;
+        let mut row = 2;
+        let mut move_to_next_row = || {
+            self.write(&format!("\x1B[{row};{column}H"));
+            row += 1;
+        };
+
+        move_to_next_row();
+        self.print(Role::Border, &format!("┌{}┐", "─".repeat(columns)));
+        for minimap_row in &rows {
+            move_to_next_row();
+            self.print(Role::Border, "│");
+            for (glyph, role) in minimap_row {
+                self.draw_braille(*glyph, *role);
+            }
+            self.print(Role::Border, "│");
+        }
+        move_to_next_row();
+        self.print(Role::Border, &format!("└{}┘", "─".repeat(columns)));
+        //back under the board, where the hud goes
+        self.write(&format!(
+            "\x1B[{};1H",
+            self.render_mode.board_extent(visible).1 + 1
+        ));
+    }
+
+    //the upper half block takes the color of the top cell and its background
+    //the one of the bottom cell, when the terminal cannot show both colors
+    //a full block is drawn instead so no cell disappears
+    fn draw_half_block(&self, top: Option<Role>, bottom: Option<Role>) {
+        let theme = self.theme.borrow();
+        let color = |role: Role| theme.color(role);
+        match (top, bottom) {
+            (None, None) => self.write(" "),
+            (Some(top), None) => self.print(top, "▀"),
+            (None, Some(bottom)) => self.print(bottom, "▄"),
+            (Some(top), Some(bottom)) => {
+                let foreground = color(top).and_then(|c| c.escape(self.color_support));
+                let background = color(bottom)
+                    .filter(|c| Some(*c) != color(top))
+                    .and_then(|c| c.background_escape(self.color_support));
+                match (foreground, background) {
+                    (Some(foreground), Some(background)) => {
+                        self.write(&format!("{foreground}{background}▀{RESET}"))
+                    }
+                    _ => self.print(top, "█"),
                 }
-            },
-            CellContent::Apple => Self::print_red("● "),
+            }
         }
     }
 
-    fn draw_game_over(score: usize) {
-        print!("\n\r");
-        print!("\n\r");
-        Self::print_red(
-            " ██████   █████  ███    ███ ███████      ██████  ██    ██ ███████ ██████  \r\n",
+    fn draw_hud(&self, hud: &Hud) {
+        let line = locale::fill(
+            self.messages().hud,
+            &[
+                ("score", &hud.score),
+                ("length", &hud.length),
+                ("apples", &hud.apples),
+                ("speed", &hud.tick_interval.as_millis()),
+                ("time", &format_time(hud)),
+                ("best", &hud.best_score),
+            ],
         );
-        Self::print_red(
-            "██       ██   ██ ████  ████ ██          ██    ██ ██    ██ ██      ██   ██ \r\n",
+        self.print(Role::Text, &format!("  {line}"));
+        self.write("\n\r");
+        self.flush();
+    }
+
+    fn draw_cell(&self, cell: &CellContent) {
+        let glyph = self.glyphs.cell(cell);
+        match cell {
+            CellContent::Empty => self.write(glyph),
+            CellContent::Head(_) => self.print(Role::Head, glyph),
+            CellContent::Tail(_) => self.print(Role::Tail, glyph),
+            CellContent::Body { .. } => self.print(Role::Body, glyph),
+            CellContent::Apple => self.print(Role::Apple, glyph),
+        }
+    }
+
+    fn draw_game_over(&self, hud: &Hud, cause: DeathCause, board_size: (usize, usize)) {
+        let messages = self.messages();
+        let cause = match cause {
+            DeathCause::Wall => messages.hit_wall,
+            DeathCause::Body => messages.hit_body,
+        };
+        let lines = self.end_lines(hud, cause);
+        self.draw_panel(Role::Apple, messages.game_over, &lines, board_size);
+    }
+
+    fn draw_victory(&self, hud: &Hud, board_size: (usize, usize)) {
+        let messages = self.messages();
+        let lines = self.end_lines(hud, messages.filled_board);
+        self.draw_panel(Role::Body, messages.victory, &lines, board_size);
+    }
+
+    fn draw_resume_offer(&self, hud: &Hud, board_size: (usize, usize)) {
+        let messages = self.messages();
+        let lines = [
+            (Role::Text, String::new()),
+            (
+                Role::Border,
+                locale::fill(messages.your_score, &[("score", &hud.score)]),
+            ),
+            (
+                Role::Text,
+                locale::fill(
+                    messages.stats,
+                    &[
+                        ("length", &hud.length),
+                        ("apples", &hud.apples),
+                        ("time", &format_time(hud)),
+                    ],
+                ),
+            ),
+            (Role::Text, String::new()),
+            (Role::Text, messages.resume_hint.to_string()),
+        ];
+        self.draw_panel(Role::Body, messages.saved_game, &lines, board_size);
+    }
+
+    //the score, how the game ended and its stats
+    fn end_lines(&self, hud: &Hud, ending: &str) -> [(Role, String); 6] {
+        let messages = self.messages();
+        [
+            (Role::Text, String::new()),
+            (
+                Role::Border,
+                locale::fill(messages.your_score, &[("score", &hud.score)]),
+            ),
+            (Role::Text, ending.to_string()),
+            (
+                Role::Text,
+                locale::fill(
+                    messages.stats,
+                    &[
+                        ("length", &hud.length),
+                        ("apples", &hud.apples),
+                        ("time", &format_time(hud)),
+                    ],
+                ),
+            ),
+            (Role::Text, String::new()),
+            (Role::Text, messages.continue_or_quit.to_string()),
+        ]
+    }
+
+    fn draw_new_high_score(
+        &self,
+        hud: &Hud,
+        rank: usize,
+        initials: &str,
+        board_size: (usize, usize),
+    ) {
+        let messages = self.messages();
+        let initials = format!("{initials:_<INITIALS_LEN$}");
+        let lines = [
+            (Role::Text, String::new()),
+            (
+                Role::Border,
+                locale::fill(messages.your_score, &[("score", &hud.score)]),
+            ),
+            (
+                Role::Text,
+                locale::fill(messages.rank, &[("rank", &(rank + 1))]),
+            ),
+            (Role::Text, String::new()),
+            (
+                Role::Head,
+                locale::fill(messages.your_initials, &[("initials", &initials)]),
+            ),
+            (Role::Text, String::new()),
+            (Role::Text, messages.initials_hint.to_string()),
+        ];
+        self.draw_panel(Role::Body, messages.new_high_score, &lines, board_size);
+    }
+
+    fn draw_high_scores(&self, settings: &str, scores: &[HighScore]) {
+        // clears the screen using ANSI escape codes
+        self.write("\x1B[2J\x1B[1;1H");
+
+        let messages = self.messages();
+        self.print(Role::Body, &format!("{}\r\n", messages.high_scores));
+        self.print(Role::Border, &format!("{settings}\r\n"));
+        self.write("\n\r");
+        if scores.is_empty() {
+            self.print(Role::Text, &format!("{}\r\n", messages.no_scores));
+        }
+        for (rank, high_score) in scores.iter().enumerate() {
+            self.print(
+                Role::Text,
+                &format!(
+                    "{:>4}. {:<5}{:>8}\r\n",
+                    rank + 1,
+                    high_score.initials,
+                    high_score.score
+                ),
+            );
+        }
+        self.write("\n\r");
+        self.print(Role::Text, &format!("{}\r\n", messages.back_hint));
+
+        self.flush()
+    }
+
+    fn draw_stats(&self, stats: &Stats) {
+        // clears the screen using ANSI escape codes
+        self.write("\x1B[2J\x1B[1;1H");
+
+        let messages = self.messages();
+        self.print(Role::Body, &format!("{}\r\n", messages.lifetime_stats));
+        self.write("\n\r");
+        for line in stats.lines(messages) {
+            self.print(Role::Text, &format!("  {line}\r\n"));
+        }
+        self.write("\n\r");
+        self.print(Role::Text, &format!("{}\r\n", messages.back_hint));
+
+        self.flush()
+    }
+
+    fn draw_pause(&self) {
+        self.write("\n\r");
+        self.print(
+            Role::Border,
+            &format!("                  {}\r\n", self.messages().paused),
         );
-        Self::print_red(
-            "██   ███ ███████ ██ ████ ██ █████       ██    ██ ██    ██ █████   ██████  \r\n",
+        self.flush()
+    }
+
+    fn draw_menu_overlay(&self, title: &str, menu: &[MenuEntry], board_size: (usize, usize)) {
+        let lines: Vec<(Role, String)> = menu
+            .iter()
+            .map(|entry| {
+                let mut line = Self::format_entry(entry);
+                if let Some(error) = &entry.error {
+                    line += &format!("  {error}");
+                }
+                let role = if entry.error.is_some() {
+                    Role::Apple
+                } else if entry.selected {
+                    Role::Body
+                } else {
+                    Role::Text
+                };
+                (role, line)
+            })
+            .collect();
+        self.draw_panel(Role::Border, title, &lines, board_size);
+    }
+
+    //a box with a title in its top wall, centered on the board
+    fn draw_panel(
+        &self,
+        frame: Role,
+        title: &str,
+        lines: &[(Role, String)],
+        (width, height): (usize, usize),
+    ) {
+        let inner_width = lines
+            .iter()
+            .map(|(_, line)| line.chars().count())
+            .chain([title.chars().count() + 2])
+            .max()
+            .unwrap_or(0)
+            + 2;
+
+        let visible = self.visible_size((width, height));
+        let (board_columns, board_rows) = self.render_mode.board_extent(visible);
+        let column = board_columns.saturating_sub(inner_width + 2) / 2 + 1;
+        let mut row = board_rows.saturating_sub(lines.len() + 2) / 2 + 1;
+
+        let title = format!(" {title} ");
+        let mut move_to_next_row = || {
+            self.write(&format!("\x1B[{row};{column}H"));
+            row += 1;
+        };
+
+        move_to_next_row();
+        self.print(frame, &format!("┌{title:─^inner_width$}┐"));
+        for (role, line) in lines {
+            move_to_next_row();
+            self.print(frame, "│");
+            self.print(*role, &format!(" {line:<0$}", inner_width - 1));
+            self.print(frame, "│");
+        }
+        move_to_next_row();
+        self.print(frame, &format!("└{}┘", "─".repeat(inner_width)));
+
+        //leave the cursor under the board and the hud
+        self.write(&format!("\x1B[{};1H", board_rows + 2));
+        self.flush()
+    }
+
+    fn draw_debug_overlay(&self, lines: &[String]) {
+        for line in lines {
+            self.print(Role::Border, line);
+            self.write("\n\r");
+        }
+        self.flush()
+    }
+
+    fn draw_title_screen(&self, menu: &[MenuEntry], controls: &[(String, &'static str)]) {
+        // clears the screen using ANSI escape codes
+        self.write("\x1B[2J\x1B[1;1H");
+
+        let messages = self.messages();
+        self.print(Role::Text, &format!("{}\r\n", messages.welcome));
+        self.write("\n\r");
+        self.write("\n\r");
+        for line in BANNER {
+            self.print(Role::Body, &format!("{line}\r\n"));
+        }
+        self.write("\n\r");
+        self.write("\n\r");
+        self.draw_menu_entries(menu);
+        self.write("\n\r");
+        self.print(
+            Role::Text,
+            &format!(
+                "                                          {}\r\n",
+                messages.controls_heading
+            ),
         );
-        Self::print_red(
-            "██    ██ ██   ██ ██  ██  ██ ██          ██    ██  ██  ██  ██      ██   ██ \r\n",
+        self.print(
+            Role::Text,
+            &format!(
+                "                                            {}\r\n",
+                messages.menu_hint
+            ),
         );
-        Self::print_red(
-            " ██████  ██   ██ ██      ██ ███████      ██████    ████   ███████ ██   ██ \r\n",
+        for (key, description) in controls {
+            self.print(
+                Role::Text,
+                &format!("                                            {key:<10}{description}\r\n"),
+            );
+        }
+        self.write("\n");
+        self.print(
+            Role::Border,
+            &format!(
+                "                                          {}\r",
+                messages.start_hint
+            ),
         );
-        print!("\n\r");
-        print!("\n\r");
-        println!("                  press enter to continue, esc to quit\r");
-        println!("\r");
-        Self::print_blue(&format!("                        your score is: {score}\r"));
 
-        io::stdout().flush().expect("failed to flush")
+        self.flush()
     }
 
-    fn draw_title_screen() {
+    fn draw_controls(&self, bindings: &[MenuEntry], message: Option<&str>) {
         // clears the screen using ANSI escape codes
-        print!("\x1B[2J\x1B[1;1H");
-
-        println!("Welcome to:\r");
-        print!("\n\r");
-        print!("\n\r");
-        Self::print_green("██████  ██    ██ ███████ ████████ ██    ██     ██████   █████  ████████ ████████ ██      ███████ \r\n");
-        Self::print_green("██   ██ ██    ██ ██         ██     ██  ██      ██   ██ ██   ██    ██       ██    ██      ██      \r\n");
-        Self::print_green("██████  ██    ██ ███████    ██      ████       ██████  ███████    ██       ██    ██      █████   \r\n");
-        Self::print_green("██   ██ ██    ██      ██    ██       ██        ██   ██ ██   ██    ██       ██    ██      ██      \r\n");
-        Self::print_green("██   ██  ██████  ███████    ██       ██        ██   ██ ██   ██    ██       ██    ███████ ███████ \r\n");
-        print!("\n\r");
-        print!("\n\r");
-        println!("                                          controls:\r");
-        println!("                                            wads to move\r");
-        println!("                                            esc to quit\r");
-        println!("");
-        Self::print_blue("                                          press enter to continue\r");
-    }
-
-    fn print_green(s: &str) {
-        print!("{GREEN}");
-        print!("{s}");
-        print!("{WHITE}");
-    }
-    fn print_red(s: &str) {
-        print!("{RED}");
-        print!("{s}");
-        print!("{WHITE}");
-    }
-    fn print_blue(s: &str) {
-        print!("{BLUE}");
-        print!("{s}");
-        print!("{WHITE}");
+        self.write("\x1B[2J\x1B[1;1H");
+
+        let messages = self.messages();
+        self.print(Role::Body, &format!("{}\r\n", messages.controls));
+        self.write("\n\r");
+        self.draw_menu_entries(bindings);
+        self.write("\n\r");
+        if let Some(message) = message {
+            self.print(Role::Apple, message);
+        }
+        self.write("\n\r");
+        self.print(Role::Text, &format!("{}\r\n", messages.controls_hint));
+
+        self.flush()
+    }
+
+    fn draw_menu_entries(&self, menu: &[MenuEntry]) {
+        for entry in menu {
+            let line = format!(
+                "                                        {}",
+                Self::format_entry(entry)
+            );
+            if entry.selected {
+                self.print(Role::Border, &line);
+            } else {
+                self.print(Role::Text, &line);
+            }
+            if let Some(error) = &entry.error {
+                self.print(Role::Apple, &format!("  {error}"));
+            }
+            self.write("\n\r");
+        }
+    }
+
+    fn format_entry(entry: &MenuEntry) -> String {
+        let cursor = if entry.selected { ">" } else { " " };
+        match entry.value.as_str() {
+            "" => format!("{cursor} {}", entry.label),
+            value => format!("{cursor} {:<20}< {value:^11} >", entry.label),
+        }
     }
+
+    //besides the board, the body color is used for titles and highlights,
+    //the apple color for errors and the border color for hints
+    fn print(&self, role: Role, s: &str) {
+        let color = self.theme.borrow().color(role);
+        match color.and_then(|color| color.escape(self.color_support)) {
+            Some(escape) => self.write(&format!("{escape}{s}{RESET}")),
+            None => self.write(s),
+        }
+    }
+}
+
+//mm:ss of the game clock
+fn format_time(hud: &Hud) -> String {
+    let seconds = hud.elapsed.as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
 }
 
+#[cfg(test)]
 mod tests;
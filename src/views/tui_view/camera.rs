@@ -0,0 +1,51 @@
+//the window of the board shown when it does not fit on the screen,
+//it follows the head once it gets inside the dead zones along the edges
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Camera {
+    origin: (usize, usize),
+    //how many cells from the edges of the window the head can get before it scrolls
+    dead_zone: (usize, usize),
+}
+
+impl Camera {
+    pub fn new(dead_zone: (usize, usize)) -> Self {
+        Camera {
+            origin: (0, 0),
+            dead_zone,
+        }
+    }
+
+    //moves the window of view cells over a board of board cells to keep the head
+    //out of the dead zones, returns the top left cell of the window
+    pub fn follow(
+        &mut self,
+        head: (usize, usize),
+        view: (usize, usize),
+        board: (usize, usize),
+    ) -> (usize, usize) {
+        self.origin = (
+            follow_axis(self.origin.0, head.0, view.0, board.0, self.dead_zone.0),
+            follow_axis(self.origin.1, head.1, view.1, board.1, self.dead_zone.1),
+        );
+        self.origin
+    }
+}
+
+fn follow_axis(origin: usize, head: usize, view: usize, board: usize, dead_zone: usize) -> usize {
+    if view >= board {
+        return 0;
+    }
+    //a dead zone of half the window or more would never let the head stand still
+    let dead_zone = dead_zone.min(view.saturating_sub(1) / 2);
+    let origin = if head < origin + dead_zone {
+        head.saturating_sub(dead_zone)
+    } else if head + dead_zone >= origin + view {
+        head + dead_zone + 1 - view
+    } else {
+        origin
+    };
+    origin.min(board - view)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,38 @@
+use super::Camera;
+
+#[test]
+fn test_camera_stays_until_the_dead_zone() {
+    let mut camera = Camera::new((2, 1));
+    let view = (10, 5);
+    let board = (30, 20);
+    assert_eq!(camera.follow((5, 2), view, board), (0, 0));
+    assert_eq!(camera.follow((7, 3), view, board), (0, 0));
+
+    //two cells from the right edge the window starts scrolling
+    assert_eq!(camera.follow((8, 3), view, board), (1, 0));
+    assert_eq!(camera.follow((9, 4), view, board), (2, 1));
+
+    //going back, the window stays until the head reaches the left dead zone
+    assert_eq!(camera.follow((5, 3), view, board), (2, 1));
+    assert_eq!(camera.follow((3, 3), view, board), (1, 1));
+}
+
+#[test]
+fn test_camera_stays_on_the_board() {
+    let mut camera = Camera::new((4, 4));
+    let view = (10, 5);
+    let board = (30, 20);
+    assert_eq!(camera.follow((29, 19), view, board), (20, 15));
+    assert_eq!(camera.follow((0, 0), view, board), (0, 0));
+
+    //the snake coming out of the opposite wall moves the window there at once
+    assert_eq!(camera.follow((29, 0), view, board), (20, 0));
+}
+
+#[test]
+fn test_camera_without_scrolling() {
+    //a board that fits in the window is never scrolled
+    let mut camera = Camera::new((2, 2));
+    assert_eq!(camera.follow((9, 4), (10, 5), (10, 5)), (0, 0));
+    assert_eq!(camera.follow((9, 4), (12, 5), (10, 5)), (0, 0));
+}
@@ -0,0 +1,79 @@
+use crate::{model::CellContent, views::theme::Role};
+
+//the first of the braille patterns, every other one adds a dot per bit
+const BRAILLE_BLANK: u32 = 0x2800;
+
+//the bit of each dot of a braille pattern, by column and row
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+//when a braille character holds different things, the first one in this list gives the color
+const BRAILLE_PRIORITY: [Role; 4] = [Role::Head, Role::Apple, Role::Body, Role::Tail];
+
+pub fn cell_role(cell: &CellContent) -> Option<Role> {
+    match cell {
+        CellContent::Empty => None,
+        CellContent::Head(_) => Some(Role::Head),
+        CellContent::Tail(_) => Some(Role::Tail),
+        CellContent::Body { .. } => Some(Role::Body),
+        CellContent::Apple => Some(Role::Apple),
+    }
+}
+
+//the cell standing for a group of cells in a smaller picture of the board
+pub fn most_visible<'a>(cells: impl Iterator<Item = &'a CellContent>) -> CellContent {
+    cells
+        .filter_map(|cell| Some((cell_role(cell)?, cell)))
+        .min_by_key(|(role, _)| BRAILLE_PRIORITY.iter().position(|other| other == role))
+        .map_or(CellContent::Empty, |(_, cell)| cell.clone())
+}
+
+//pairs of cells on top of each other, a row of pairs for every two rows of the grid,
+//the bottom of the last pair is empty when the grid has an odd number of rows
+pub fn half_blocks(grid: &[Vec<CellContent>]) -> Vec<Vec<(Option<Role>, Option<Role>)>> {
+    grid.chunks(2)
+        .map(|rows| {
+            let bottom = rows.get(1);
+            rows[0]
+                .iter()
+                .enumerate()
+                .map(|(x, top)| {
+                    let bottom = bottom.and_then(|row| cell_role(&row[x]));
+                    (cell_role(top), bottom)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+//a braille character for every block of 2x4 cells, with the role that colors it
+pub fn braille(grid: &[Vec<CellContent>]) -> Vec<Vec<(char, Option<Role>)>> {
+    let width = grid.first().map_or(0, |row| row.len());
+    grid.chunks(4)
+        .map(|rows| {
+            (0..width.div_ceil(2))
+                .map(|column| {
+                    let mut pattern = 0;
+                    let mut roles = Vec::new();
+                    for (dy, row) in rows.iter().enumerate() {
+                        for (dx, dots) in BRAILLE_DOTS.iter().enumerate() {
+                            let role = row.get(column * 2 + dx).and_then(cell_role);
+                            if let Some(role) = role {
+                                pattern |= dots[dy];
+                                roles.push(role);
+                            }
+                        }
+                    }
+                    let role = BRAILLE_PRIORITY
+                        .into_iter()
+                        .find(|role| roles.contains(role));
+                    let glyph = char::from_u32(BRAILLE_BLANK + pattern)
+                        .expect("braille patterns are valid characters");
+                    (glyph, role)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,42 @@
+use super::{braille, half_blocks};
+use crate::{
+    model::{CellContent, Orientation},
+    views::theme::Role,
+};
+
+fn test_grid() -> Vec<Vec<CellContent>> {
+    let mut grid = vec![vec![CellContent::Empty; 6]; 3];
+    grid[0][0] = CellContent::Tail(Orientation::Down);
+    grid[0][5] = CellContent::Apple;
+    grid[1][0] = CellContent::Body {
+        towards: Orientation::Right,
+        from: Orientation::Down,
+    };
+    grid[1][1] = CellContent::Head(Orientation::Right);
+    grid[2][1] = CellContent::Apple;
+    grid
+}
+
+#[test]
+fn test_half_blocks() {
+    let rows = half_blocks(&test_grid());
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].len(), 6);
+    assert_eq!(rows[0][0], (Some(Role::Tail), Some(Role::Body)));
+    assert_eq!(rows[0][1], (None, Some(Role::Head)));
+    assert_eq!(rows[0][2], (None, None));
+    assert_eq!(rows[0][5], (Some(Role::Apple), None));
+    //the last row has nothing under it
+    assert_eq!(rows[1][1], (Some(Role::Apple), None));
+}
+
+#[test]
+fn test_braille() {
+    let rows = braille(&test_grid());
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].len(), 3);
+    //dots 1, 2 and 5 for the snake, dot 6 for the apple, the head gives the color
+    assert_eq!(rows[0][0], ('\u{2833}', Some(Role::Head)));
+    assert_eq!(rows[0][1], ('\u{2800}', None));
+    assert_eq!(rows[0][2], ('\u{2808}', Some(Role::Apple)));
+}
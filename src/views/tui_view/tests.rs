@@ -1,81 +1,267 @@
-#[allow(unused_imports)]
-use std::{thread, time::Duration};
+use std::time::Duration;
 
-#[allow(unused_imports)]
 use crate::{
-    model::{CellContent, Orientation},
-    views::tui_view::TuiView,
+    locale::Locale,
+    model::{
+        animation::Effect,
+        render_state::{Board, RenderState, Screen},
+        title_menu::MenuEntry,
+        CellContent, DeathCause, Hud, Orientation,
+    },
+    settings::GameMode,
+    stats::Stats,
+    views::{
+        glyphs::GlyphSet,
+        theme::{ColorSupport, Role},
+        tui_view::{RenderMode, ScreenSize, TuiView},
+        Theme, View,
+    },
 };
 
-#[test]
-fn test_draw_cell() {
-    //to not overlap it with test_draw_grid()
-    thread::sleep(Duration::from_secs(2));
-
-    print!("\n\n\n");
-    CellContent::Empty
-        .into_iter()
-        .for_each(|cell| TuiView::draw_cell(&cell));
-    print!("\n\n\n");
+//renders with a fresh view into a buffer
+fn render(theme: &str, draw: impl FnOnce(&TuiView<Vec<u8>>)) -> String {
+    let view = TuiView::with_writer(Vec::new());
+    view.set_theme(&Theme::named(theme).unwrap().unwrap());
+    draw(&view);
+    String::from_utf8(view.into_inner()).unwrap()
 }
 
-#[test]
-fn test_draw_grid() {
-    use super::TuiView;
-    use crate::model::CellContent;
+fn test_grid() -> Vec<Vec<CellContent>> {
+    let mut grid = vec![vec![CellContent::Empty; 6]; 4];
 
-    let mut grid = vec![vec![CellContent::Empty; 30]; 20];
-
-    grid[1][1] = CellContent::Apple;
-    grid[10][10] = CellContent::Head(Orientation::Up);
-    grid[11][10] = CellContent::Body {
-        towards: Orientation::Up,
-        from: Orientation::Up,
-    };
-    grid[12][10] = CellContent::Body {
+    grid[0][5] = CellContent::Apple;
+    grid[1][1] = CellContent::Head(Orientation::Up);
+    grid[2][1] = CellContent::Body {
         towards: Orientation::Up,
         from: Orientation::Right,
     };
-    grid[12][9] = CellContent::Body {
-        towards: Orientation::Right,
-        from: Orientation::Right,
-    };
-    grid[12][8] = CellContent::Body {
+    grid[2][0] = CellContent::Body {
         towards: Orientation::Right,
         from: Orientation::Down,
     };
-    grid[11][8] = CellContent::Body {
-        towards: Orientation::Down,
-        from: Orientation::Down,
-    };
-    grid[10][8] = CellContent::Body {
+    grid[1][0] = CellContent::Body {
         towards: Orientation::Down,
         from: Orientation::Down,
     };
-    grid[9][8] = CellContent::Body {
-        towards: Orientation::Down,
-        from: Orientation::Left,
+    grid[0][0] = CellContent::Tail(Orientation::Down);
+    grid
+}
+
+fn test_menu() -> Vec<MenuEntry> {
+    vec![
+        MenuEntry {
+            label: "resume",
+            value: String::new(),
+            error: None,
+            selected: true,
+        },
+        MenuEntry {
+            label: "height",
+            value: "5".to_string(),
+            error: Some("between 8 and 100".to_string()),
+            selected: false,
+        },
+    ]
+}
+
+#[test]
+fn test_draw_cell() {
+    let output = render("monochrome", |view| {
+        CellContent::Empty
+            .into_iter()
+            .for_each(|cell| view.draw_cell(cell))
+    });
+    assert_eq!(output, include_str!("golden/cells.txt"));
+}
+
+#[test]
+fn test_draw_grid() {
+    let output = render("color", |view| view.draw_grid(&test_grid(), &[]));
+    assert_eq!(output, include_str!("golden/grid.txt"));
+}
+
+#[test]
+fn test_draw_grid_monochrome() {
+    let output = render("monochrome", |view| view.draw_grid(&test_grid(), &[]));
+    assert_eq!(output, include_str!("golden/grid_monochrome.txt"));
+}
+
+#[test]
+fn test_draw_menu_overlay() {
+    let output = render("color", |view| {
+        view.draw_menu_overlay("menu", &test_menu(), (20, 10))
+    });
+    assert_eq!(output, include_str!("golden/menu_overlay.txt"));
+}
+
+#[test]
+fn test_draw_game_over() {
+    let hud = Hud {
+        score: 42,
+        length: 9,
+        apples: 5,
+        elapsed: Duration::from_secs(75),
+        ..Hud::default()
     };
-    grid[9][9] = CellContent::Body {
-        towards: Orientation::Left,
-        from: Orientation::Left,
+    let output = render("color", |view| {
+        view.draw_game_over(&hud, DeathCause::Body, (20, 10))
+    });
+    assert_eq!(output, include_str!("golden/game_over.txt"));
+}
+
+#[test]
+fn test_draw_new_high_score() {
+    let hud = Hud {
+        score: 42,
+        ..Hud::default()
     };
-    grid[9][10] = CellContent::Body {
-        towards: Orientation::Left,
-        from: Orientation::Left,
+    let output = render("monochrome", |view| {
+        view.draw_new_high_score(&hud, 2, "AB", (20, 10))
+    });
+    assert!(output.contains(" NEW HIGH SCORE "));
+    assert!(output.contains("your score is: 42"));
+    assert!(output.contains("number 3 of the best scores"));
+    assert!(output.contains("your initials: AB_"));
+}
+
+#[test]
+fn test_draw_stats() {
+    let mut stats = Stats::default();
+    stats.record_game(2, 6, Duration::from_secs(30), Some(DeathCause::Body));
+    let output = render("monochrome", |view| {
+        view.set_locale(Locale::Italian);
+        view.draw_stats(&stats)
+    });
+    assert!(output.contains("statistiche"));
+    assert!(output.contains("  partite giocate: 1\r\n"));
+    assert!(output.contains("  tempo di gioco: 0:00:30\r\n"));
+    assert!(output.contains("    ti sei morso la coda: 1\r\n"));
+}
+
+#[test]
+fn test_draw_game_over_in_italian() {
+    let hud = Hud {
+        score: 42,
+        elapsed: Duration::from_secs(75),
+        ..Hud::default()
     };
-    grid[9][11] = CellContent::Body {
-        towards: Orientation::Left,
-        from: Orientation::Left,
+    let output = render("monochrome", |view| {
+        view.set_locale(Locale::Italian);
+        view.draw_game_over(&hud, DeathCause::Wall, (20, 10))
+    });
+    assert!(output.contains(" PARTITA FINITA "));
+    assert!(output.contains("il tuo punteggio: 42"));
+    assert!(output.contains("hai sbattuto contro il muro"));
+    assert!(output.contains("tempo 01:15"));
+    assert!(output.contains("premi invio per continuare, esc per uscire"));
+}
+
+#[test]
+fn test_draw_grid_without_colors() {
+    //with NO_COLOR set, every theme looks like the monochrome one
+    let view = TuiView::with_writer(Vec::new()).with_color_support(ColorSupport::None);
+    view.draw_grid(&test_grid(), &[]);
+    let output = String::from_utf8(view.into_inner()).unwrap();
+    assert_eq!(output, include_str!("golden/grid_monochrome.txt"));
+}
+
+#[test]
+fn test_draw_grid_ascii() {
+    let view = TuiView::with_writer(Vec::new()).with_glyphs(GlyphSet::Ascii);
+    view.set_theme(&Theme::named("monochrome").unwrap().unwrap());
+    view.draw_grid(&test_grid(), &[]);
+    let output = String::from_utf8(view.into_inner()).unwrap();
+    assert_eq!(output, include_str!("golden/grid_ascii.txt"));
+}
+
+#[test]
+fn test_draw_frame_with_hud() {
+    let hud = Hud {
+        score: 30,
+        length: 7,
+        apples: 3,
+        tick_interval: Duration::from_millis(200),
+        elapsed: Duration::from_secs(83),
+        best_score: 50,
     };
-    grid[9][12] = CellContent::Body {
-        towards: Orientation::Left,
-        from: Orientation::Up,
+    let board = Board {
+        grid: test_grid(),
+        hud,
+        effects: Vec::new(),
+        mode: GameMode::Classic,
     };
-    grid[10][12] = CellContent::Body {
-        towards: Orientation::Up,
-        from: Orientation::Up,
+    let output = render("monochrome", |view| {
+        view.render(&RenderState::new(Screen::Playing(board)))
+    });
+    assert_eq!(
+        output,
+        include_str!("golden/grid_monochrome.txt").to_string()
+            + "  score 30  length 7  apples 3  speed 200ms  time 01:23  best 50\n\r"
+    );
+}
+
+#[test]
+fn test_draw_grid_with_pulse() {
+    //the ring only covers the empty cells inside the board
+    let pulse = Effect::Pulse {
+        cell: (5, 0),
+        radius: 1,
     };
-    grid[11][12] = CellContent::Tail(Orientation::Up);
-    TuiView::draw_grid(grid);
+    let output = render("monochrome", |view| view.draw_grid(&test_grid(), &[pulse]));
+    let expected = include_str!("golden/grid_monochrome.txt")
+        .replacen("╷         ● ", "╷       · ● ", 1)
+        .replacen("┃ ▲         ", "┃ ▲     · · ", 1);
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_draw_grid_half_block() {
+    let view = TuiView::with_writer(Vec::new()).with_render_mode(RenderMode::HalfBlock);
+    view.set_theme(&Theme::named("monochrome").unwrap().unwrap());
+    view.draw_grid(&test_grid(), &[]);
+    let output = String::from_utf8(view.into_inner()).unwrap();
+    assert_eq!(
+        output,
+        "\x1b[2J\x1b[1;1H┌──────┐\n\r│█▄   ▀│\n\r│▀▀    │\n\r└──────┘\n\r"
+    );
+}
+
+#[test]
+fn test_draw_grid_braille() {
+    let view = TuiView::with_writer(Vec::new()).with_render_mode(RenderMode::Braille);
+    view.set_theme(&Theme::named("monochrome").unwrap().unwrap());
+    view.draw_grid(&test_grid(), &[]);
+    let output = String::from_utf8(view.into_inner()).unwrap();
+    assert_eq!(
+        output,
+        "\x1b[2J\x1b[1;1H┌───┐\n\r│\u{2837}\u{2800}\u{2808}│\n\r└───┘\n\r"
+    );
+}
+
+#[test]
+fn test_half_block_colors() {
+    //the top cell colors the block and the bottom one its background
+    let output = render("colorblind", |view| {
+        view.draw_half_block(Some(Role::Head), Some(Role::Apple))
+    });
+    assert_eq!(output, "\x1b[38;2;86;180;233m\x1b[48;2;230;159;0m▀\x1b[0m");
+
+    //without colors both cells would look the same, so the whole character is filled
+    let view = TuiView::with_writer(Vec::new()).with_color_support(ColorSupport::None);
+    view.set_theme(&Theme::named("colorblind").unwrap().unwrap());
+    view.draw_half_block(Some(Role::Head), Some(Role::Apple));
+    assert_eq!(String::from_utf8(view.into_inner()).unwrap(), "█");
+}
+
+#[test]
+fn test_draw_grid_cropped() {
+    //room for 3x2 cells, the walls on the sides hiding the rest of the board are dotted
+    let view = TuiView::with_writer(Vec::new()).with_camera(ScreenSize::Fixed(10, 7), (0, 0));
+    view.set_theme(&Theme::named("monochrome").unwrap().unwrap());
+    view.draw_grid(&test_grid(), &[]);
+    let output = String::from_utf8(view.into_inner()).unwrap();
+    assert_eq!(
+        output,
+        "\x1b[2J\x1b[1;1H╔═══════╗ \n\r║ ╷     ┆ \n\r║ ┃ ▲   ┆ \n\r╚═┄┄┄┄┄┄╝ \n\r"
+    );
 }
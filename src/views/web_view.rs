@@ -0,0 +1,479 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        mpsc::{self, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::Instant,
+};
+
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    event_processes::Event,
+    high_scores::HighScore,
+    locale::{Locale, Messages},
+    model::{
+        render_state::{Board, RenderState, Screen},
+        title_menu::MenuEntry,
+        CellContent, DeathCause,
+    },
+};
+
+use super::{theme::Role, Theme, View};
+use websocket::Message;
+
+mod websocket;
+
+const INDEX_HTML: &str = include_str!("web_view/index.html");
+
+//what the page receives, a json object in every websocket message
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Update<'a> {
+    //the roles without a color are drawn in the default color of the page
+    Theme {
+        colors: BTreeMap<&'static str, String>,
+    },
+    //the texts of the page, filled in by the page itself
+    Locale {
+        code: &'static str,
+        messages: &'static Messages,
+    },
+    Title {
+        menu: &'a [MenuEntry],
+        controls: &'a [(String, &'static str)],
+    },
+    Controls {
+        bindings: &'a [MenuEntry],
+        message: Option<&'a str>,
+    },
+    //a character for every cell, see cell_char
+    Frame {
+        rows: Vec<String>,
+        hud: HudUpdate,
+        effects: Vec<(usize, usize)>,
+    },
+    GameOver {
+        score: usize,
+        cause: DeathCause,
+    },
+    Victory {
+        score: usize,
+    },
+    NewHighScore {
+        score: usize,
+        rank: usize,
+        initials: &'a str,
+    },
+    ResumeOffer {
+        score: usize,
+    },
+    Pause,
+    Menu {
+        title: &'a str,
+        entries: &'a [MenuEntry],
+    },
+    Debug {
+        lines: &'a [String],
+    },
+    HighScores {
+        settings: &'a str,
+        scores: &'a [HighScore],
+    },
+    Stats {
+        games_played: u64,
+        apples: u64,
+        longest_snake: usize,
+        play_time_secs: u64,
+        average_game_secs: u64,
+        wall: u64,
+        body: u64,
+        wins: u64,
+    },
+}
+
+#[derive(Serialize)]
+struct HudUpdate {
+    score: usize,
+    length: usize,
+    apples: usize,
+    speed_ms: u128,
+    elapsed_secs: u64,
+    best_score: usize,
+}
+
+//what the page sends when a key is pressed, with the name the browser gives it
+#[derive(Deserialize)]
+struct KeyPress {
+    key: String,
+}
+
+#[derive(Default)]
+struct Clients {
+    senders: Vec<Sender<Message>>,
+    //what a page connecting now needs to show the current screen
+    theme: Option<String>,
+    locale: Option<String>,
+    screen: Vec<String>,
+}
+
+//a view for browsers: an http server serves a page with a canvas, which gets
+//every frame over a websocket and sends back the keys pressed
+pub struct WebView {
+    local_addr: SocketAddr,
+    clients: Arc<Mutex<Clients>>,
+}
+
+impl WebView {
+    //serves the page until the game quits, the keys pressed in the browser
+    //are sent as input events like the ones from the terminal
+    pub fn bind(addr: SocketAddr, events: Sender<Event>) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let clients = Arc::new(Mutex::new(Clients::default()));
+
+        let clients_clone = clients.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let clients = clients_clone.clone();
+                let events = events.clone();
+                //a browser closing the page is not an error worth reporting
+                thread::spawn(move || serve_client(stream, clients, events));
+            }
+        });
+
+        Ok(WebView {
+            local_addr,
+            clients,
+        })
+    }
+
+    //the port is chosen by the system when binding to port 0
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    //sent to every page, and to the ones connecting later before the screen
+    fn send_setting(&self, update: &Update, setting: fn(&mut Clients) -> &mut Option<String>) {
+        let json = serde_json::to_string(update).expect("updates should serialize");
+        let mut clients = self.clients.lock().unwrap();
+        *setting(&mut clients) = Some(json.clone());
+        clients
+            .senders
+            .retain(|sender| sender.send(Message::Text(json.clone())).is_ok());
+    }
+
+    //a new screen replaces what pages connecting later are sent first,
+    //an overlay is added to it
+    fn send(&self, update: &Update, new_screen: bool) {
+        let json = serde_json::to_string(update).expect("updates should serialize");
+        let mut clients = self.clients.lock().unwrap();
+        if new_screen {
+            clients.screen.clear();
+        }
+        clients.screen.push(json.clone());
+        //forget the pages that were closed
+        clients
+            .senders
+            .retain(|sender| sender.send(Message::Text(json.clone())).is_ok());
+    }
+}
+
+impl View for WebView {
+    fn set_theme(&self, theme: &Theme) {
+        let colors = Role::ALL
+            .into_iter()
+            .filter_map(|role| {
+                let [r, g, b] = theme.color(role)?.rgb();
+                Some((role.name(), format!("#{r:02x}{g:02x}{b:02x}")))
+            })
+            .collect();
+        self.send_setting(&Update::Theme { colors }, |clients| &mut clients.theme);
+    }
+
+    fn set_locale(&self, locale: Locale) {
+        let update = Update::Locale {
+            code: locale.code(),
+            messages: locale.messages(),
+        };
+        self.send_setting(&update, |clients| &mut clients.locale);
+    }
+
+    //the board first, then what is drawn on top of it
+    fn render(&self, state: &RenderState) {
+        if let Some(board) = state.board() {
+            self.send(&frame(board), true);
+        }
+        match &state.screen {
+            Screen::Title { menu, controls } => self.send(&Update::Title { menu, controls }, true),
+            Screen::Controls { bindings, message } => self.send(
+                &Update::Controls {
+                    bindings,
+                    message: message.as_deref(),
+                },
+                true,
+            ),
+            Screen::Playing(_) => {}
+            Screen::Paused(_) => self.send(&Update::Pause, false),
+            Screen::Menu { title, entries, .. } => {
+                self.send(&Update::Menu { title, entries }, false)
+            }
+            Screen::GameOver { board, cause } => self.send(
+                &Update::GameOver {
+                    score: board.hud.score,
+                    cause: *cause,
+                },
+                false,
+            ),
+            Screen::Victory(board) => self.send(
+                &Update::Victory {
+                    score: board.hud.score,
+                },
+                false,
+            ),
+            Screen::NewHighScore {
+                board,
+                rank,
+                initials,
+            } => self.send(
+                &Update::NewHighScore {
+                    score: board.hud.score,
+                    rank: rank + 1,
+                    initials,
+                },
+                false,
+            ),
+            Screen::HighScores { settings, scores } => {
+                self.send(&Update::HighScores { settings, scores }, true)
+            }
+            Screen::Stats(stats) => self.send(
+                &Update::Stats {
+                    games_played: stats.games_played,
+                    apples: stats.apples,
+                    longest_snake: stats.longest_snake,
+                    play_time_secs: stats.play_time().as_secs(),
+                    average_game_secs: stats.average_game().as_secs(),
+                    wall: stats.deaths_by(DeathCause::Wall),
+                    body: stats.deaths_by(DeathCause::Body),
+                    wins: stats.wins,
+                },
+                true,
+            ),
+            Screen::ResumeOffer(board) => self.send(
+                &Update::ResumeOffer {
+                    score: board.hud.score,
+                },
+                false,
+            ),
+        }
+        if let Some(lines) = &state.debug {
+            self.send(&Update::Debug { lines }, false);
+        }
+    }
+}
+
+fn frame(board: &Board) -> Update<'static> {
+    let rows = board
+        .grid
+        .iter()
+        .map(|row| row.iter().map(cell_char).collect())
+        .collect();
+    let hud = &board.hud;
+    let hud = HudUpdate {
+        score: hud.score,
+        length: hud.length,
+        apples: hud.apples,
+        speed_ms: hud.tick_interval.as_millis(),
+        elapsed_secs: hud.elapsed.as_secs(),
+        best_score: hud.best_score,
+    };
+    let effects = board
+        .effects
+        .iter()
+        .flat_map(|effect| effect.cells(&board.grid))
+        .collect();
+    Update::Frame { rows, hud, effects }
+}
+
+//the head, body and tail as the first letters of their names
+fn cell_char(cell: &CellContent) -> char {
+    match cell {
+        CellContent::Empty => '.',
+        CellContent::Head(_) => 'h',
+        CellContent::Body { .. } => 'b',
+        CellContent::Tail(_) => 't',
+        CellContent::Apple => 'a',
+    }
+}
+
+//the names browsers give to the keys in KeyboardEvent.key
+fn key_code(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    match (key, chars.next(), chars.next()) {
+        ("ArrowUp", _, _) => Some(KeyCode::Up),
+        ("ArrowDown", _, _) => Some(KeyCode::Down),
+        ("ArrowLeft", _, _) => Some(KeyCode::Left),
+        ("ArrowRight", _, _) => Some(KeyCode::Right),
+        ("Enter", _, _) => Some(KeyCode::Enter),
+        ("Escape", _, _) => Some(KeyCode::Esc),
+        ("Backspace", _, _) => Some(KeyCode::Backspace),
+        ("Tab", _, _) => Some(KeyCode::Tab),
+        (_, Some(c), None) => Some(KeyCode::Char(c)),
+        _ => None,
+    }
+}
+
+//answers a single request: the page, or the websocket the page opens on /ws
+fn serve_client(
+    stream: TcpStream,
+    clients: Arc<Mutex<Clients>>,
+    events: Sender<Event>,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let (mut websocket_key, mut host, mut origin) = (None, None, None);
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = Some(value.trim().to_string());
+            match name.trim().to_lowercase().as_str() {
+                "sec-websocket-key" => websocket_key = value,
+                "host" => host = value,
+                "origin" => origin = value,
+                _ => {}
+            }
+        }
+    }
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let local_addr = stream.local_addr()?;
+    let mut stream = stream;
+    //a name of another site resolving to this address is not the game,
+    //and neither is a page of another site opening the websocket
+    let forbidden = !host.is_some_and(|host| is_local(&host, local_addr))
+        || origin.is_some_and(|origin| !is_local(origin_authority(&origin), local_addr));
+    match (path, websocket_key) {
+        _ if forbidden => respond(&mut stream, "403 Forbidden", "text/plain", "forbidden"),
+        ("/ws", Some(key)) => {
+            write!(
+                stream,
+                "HTTP/1.1 101 Switching Protocols\r\n\
+                 Upgrade: websocket\r\n\
+                 Connection: Upgrade\r\n\
+                 Sec-WebSocket-Accept: {}\r\n\r\n",
+                websocket::accept_key(&key)
+            )?;
+            serve_websocket(reader, stream, clients, events)
+        }
+        ("/" | "/index.html", _) => respond(&mut stream, "200 OK", "text/html", INDEX_HTML),
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "not found"),
+    }
+}
+
+//browsers don't keep other sites from opening a websocket to the game, and a site
+//can even make its own name point here, so the Host and the Origin the browser sends
+//must both name the address the game listens on, or the loopback names on its port
+fn is_local(authority: &str, local_addr: SocketAddr) -> bool {
+    if authority.eq_ignore_ascii_case(&local_addr.to_string()) {
+        return true;
+    }
+    //without a port it is the one of http
+    let (name, port) = match authority.rsplit_once(':') {
+        Some((name, port)) if !port.contains(']') => (name, port.parse().ok()),
+        _ => (authority, Some(80)),
+    };
+    port == Some(local_addr.port())
+        && ["localhost", "127.0.0.1", "[::1]"]
+            .iter()
+            .any(|local| name.eq_ignore_ascii_case(local))
+}
+
+//the host and port of an origin like http://localhost:8080
+fn origin_authority(origin: &str) -> &str {
+    origin
+        .split_once("://")
+        .map_or(origin, |(_, authority)| authority)
+        .trim_end_matches('/')
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: {content_type}; charset=utf-8\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+//sends the current screen and then every update to the page,
+//while turning the keys it sends into input events
+fn serve_websocket(
+    mut reader: impl Read,
+    mut writer: impl Write + Send + 'static,
+    clients: Arc<Mutex<Clients>>,
+    events: Sender<Event>,
+) -> io::Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    {
+        let mut clients = clients.lock().unwrap();
+        for update in clients
+            .theme
+            .iter()
+            .chain(&clients.locale)
+            .chain(&clients.screen)
+        {
+            let _ = sender.send(Message::Text(update.clone()));
+        }
+        clients.senders.push(sender.clone());
+    }
+
+    thread::spawn(move || {
+        for message in receiver {
+            if websocket::write_message(&mut writer, &message).is_err() || message == Message::Close
+            {
+                break;
+            }
+        }
+    });
+
+    loop {
+        match websocket::read_message(&mut reader)? {
+            Message::Text(text) => {
+                let Ok(key_press) = serde_json::from_str::<KeyPress>(&text) else {
+                    continue;
+                };
+                if let Some(key_code) = key_code(&key_press.key) {
+                    if events.send(Event::Input(key_code, Instant::now())).is_err() {
+                        break;
+                    }
+                }
+            }
+            Message::Ping(payload) => {
+                let _ = sender.send(Message::Pong(payload));
+            }
+            Message::Pong(_) => {}
+            Message::Close => {
+                let _ = sender.send(Message::Close);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
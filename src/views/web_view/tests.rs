@@ -0,0 +1,235 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
+    sync::mpsc,
+    time::Duration,
+};
+
+use crossterm::event::KeyCode;
+
+use super::{
+    is_local, key_code, origin_authority,
+    websocket::{self, Message},
+    WebView,
+};
+use crate::{
+    event_processes::Event,
+    locale::Locale,
+    model::{
+        render_state::{Board, RenderState, Screen},
+        CellContent, DeathCause, Hud, Orientation,
+    },
+    settings::GameMode,
+    views::{Theme, View},
+};
+
+//a frame as browsers send them, always masked
+fn masked_text(text: &str) -> Vec<u8> {
+    let mask = [0x12, 0x34, 0x56, 0x78];
+    let mut frame = vec![0x81, 0x80 | text.len() as u8];
+    frame.extend(mask);
+    frame.extend(text.bytes().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+    frame
+}
+
+//reads the status line and the headers of a response
+fn read_head(reader: &mut impl BufRead) -> Vec<String> {
+    let mut head = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if line.trim().is_empty() {
+            return head;
+        }
+        head.push(line.trim().to_string());
+    }
+}
+
+fn read_update(reader: &mut impl Read) -> serde_json::Value {
+    match websocket::read_message(reader).unwrap() {
+        Message::Text(text) => serde_json::from_str(&text).unwrap(),
+        message => panic!("expected a text message, got {message:?}"),
+    }
+}
+
+fn open_websocket(view: &WebView) -> (BufReader<TcpStream>, TcpStream) {
+    let mut stream = TcpStream::connect(view.local_addr()).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    write!(
+        stream,
+        "GET /ws HTTP/1.1\r\nHost: localhost:{}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n",
+        view.local_addr().port()
+    )
+    .unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let head = read_head(&mut reader);
+    assert_eq!(head[0], "HTTP/1.1 101 Switching Protocols");
+    assert!(head.contains(&"Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=".to_string()));
+    (reader, stream)
+}
+
+#[test]
+fn test_accept_key() {
+    //the example of RFC 6455
+    assert_eq!(
+        websocket::accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+        "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+    );
+}
+
+#[test]
+fn test_read_and_write_messages() {
+    assert_eq!(
+        websocket::read_message(&mut masked_text("{\"key\":\"a\"}").as_slice()).unwrap(),
+        Message::Text("{\"key\":\"a\"}".to_string())
+    );
+
+    //long messages take two more bytes for their length
+    let text = "x".repeat(300);
+    let mut frame = Vec::new();
+    websocket::write_message(&mut frame, &Message::Text(text.clone())).unwrap();
+    assert_eq!(frame[..4], [0x81, 126, 0x01, 0x2C]);
+    assert_eq!(
+        websocket::read_message(&mut frame.as_slice()).unwrap(),
+        Message::Text(text)
+    );
+
+    //binary frames are never sent by the page
+    assert!(websocket::read_message(&mut [0x82, 0x00].as_slice()).is_err());
+}
+
+#[test]
+fn test_key_names() {
+    assert_eq!(key_code("ArrowLeft"), Some(KeyCode::Left));
+    assert_eq!(key_code("Escape"), Some(KeyCode::Esc));
+    assert_eq!(key_code("p"), Some(KeyCode::Char('p')));
+    assert_eq!(key_code("é"), Some(KeyCode::Char('é')));
+    assert_eq!(key_code("Shift"), None);
+}
+
+#[test]
+fn test_serve_page() {
+    let (events, _) = mpsc::channel();
+    let view = WebView::bind("127.0.0.1:0".parse().unwrap(), events).unwrap();
+
+    let mut stream = TcpStream::connect(view.local_addr()).unwrap();
+    let host = view.local_addr();
+    write!(stream, "GET / HTTP/1.1\r\nHost: {host}\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("<canvas"));
+
+    let mut stream = TcpStream::connect(view.local_addr()).unwrap();
+    write!(stream, "GET /missing HTTP/1.1\r\nHost: {host}\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+}
+
+#[test]
+fn test_other_sites_cannot_open_the_websocket() {
+    let (events, _) = mpsc::channel();
+    let view = WebView::bind("127.0.0.1:0".parse().unwrap(), events).unwrap();
+    let port = view.local_addr().port();
+    let upgrade = |host: &str, origin: &str| {
+        let mut stream = TcpStream::connect(view.local_addr()).unwrap();
+        write!(
+            stream,
+            "GET /ws HTTP/1.1\r\nHost: {host}\r\nOrigin: {origin}\r\n\
+             Upgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n"
+        )
+        .unwrap();
+        read_head(&mut BufReader::new(stream))[0].clone()
+    };
+    let local = format!("localhost:{port}");
+    assert_eq!(
+        upgrade(&local, "https://example.com"),
+        "HTTP/1.1 403 Forbidden"
+    );
+    assert_eq!(upgrade(&local, "null"), "HTTP/1.1 403 Forbidden");
+    assert_eq!(
+        upgrade(&local, &format!("http://{local}")),
+        "HTTP/1.1 101 Switching Protocols"
+    );
+
+    //a site that made its own name point to the game agrees with itself
+    let evil = format!("evil.example:{port}");
+    assert_eq!(
+        upgrade(&evil, &format!("http://{evil}")),
+        "HTTP/1.1 403 Forbidden"
+    );
+    let mut stream = TcpStream::connect(view.local_addr()).unwrap();
+    write!(stream, "GET / HTTP/1.1\r\nHost: {evil}\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 403 Forbidden\r\n"));
+
+    //the page may be opened through the address the game listens on or a loopback name
+    let local_addr = view.local_addr();
+    assert!(is_local(&local_addr.to_string(), local_addr));
+    assert!(is_local(&format!("LOCALHOST:{port}"), local_addr));
+    assert!(is_local(&format!("[::1]:{port}"), local_addr));
+    assert!(!is_local(&format!("localhost:{}", port + 1), local_addr));
+    assert!(!is_local("localhost", local_addr));
+    assert_eq!(origin_authority("http://127.0.0.1:8080/"), "127.0.0.1:8080");
+}
+
+#[test]
+fn test_frames_and_keys_over_websocket() {
+    let (events, event_receiver) = mpsc::channel();
+    let view = WebView::bind("127.0.0.1:0".parse().unwrap(), events).unwrap();
+    view.set_theme(&Theme::named("colorblind").unwrap().unwrap());
+    view.set_locale(Locale::Italian);
+
+    let mut grid = vec![vec![CellContent::Empty; 3]; 2];
+    grid[0][1] = CellContent::Head(Orientation::Up);
+    grid[1][1] = CellContent::Tail(Orientation::Up);
+    grid[0][2] = CellContent::Apple;
+    let board = Board {
+        grid,
+        hud: Hud {
+            score: 30,
+            ..Hud::default()
+        },
+        effects: Vec::new(),
+        mode: GameMode::Classic,
+    };
+    view.render(&RenderState::new(Screen::Playing(board.clone())));
+
+    //a page connecting late gets the theme, the texts and the current screen first
+    let (mut reader, mut stream) = open_websocket(&view);
+    let theme = read_update(&mut reader);
+    assert_eq!(theme["type"], "theme");
+    assert_eq!(theme["colors"]["apple"], "#e69f00");
+    let locale = read_update(&mut reader);
+    assert_eq!(locale["type"], "locale");
+    assert_eq!(locale["code"], "it");
+    assert_eq!(locale["messages"]["game_over"], "PARTITA FINITA");
+    let frame = read_update(&mut reader);
+    assert_eq!(frame["type"], "frame");
+    assert_eq!(frame["rows"], serde_json::json!([".ha", ".t."]));
+
+    view.render(&RenderState::new(Screen::GameOver {
+        board,
+        cause: DeathCause::Body,
+    }));
+    //the board again, then the game over on top of it
+    assert_eq!(read_update(&mut reader)["type"], "frame");
+    let game_over = read_update(&mut reader);
+    assert_eq!(game_over["type"], "game_over");
+    assert_eq!(game_over["score"], 30);
+    assert_eq!(game_over["cause"], "body");
+
+    stream
+        .write_all(&masked_text(r#"{"key": "ArrowUp"}"#))
+        .unwrap();
+    match event_receiver.recv_timeout(Duration::from_secs(5)).unwrap() {
+        Event::Input(KeyCode::Up, _) => {}
+        _ => panic!("expected the up key"),
+    }
+}
@@ -0,0 +1,105 @@
+use std::io::{self, Read, Write};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+//appended to the key of the client to prove the server speaks websocket
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+//the browser only sends keys, anything bigger is not from our page
+const MAX_PAYLOAD: u64 = 64 * 1024;
+
+const TEXT: u8 = 0x1;
+const CLOSE: u8 = 0x8;
+const PING: u8 = 0x9;
+const PONG: u8 = 0xA;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    Text(String),
+    Ping(Vec<u8>),
+    Pong(Vec<u8>),
+    Close,
+}
+
+//the Sec-WebSocket-Accept header answering the Sec-WebSocket-Key of the client
+pub fn accept_key(key: &str) -> String {
+    let mut sha1 = sha1_smol::Sha1::new();
+    sha1.update(key.trim().as_bytes());
+    sha1.update(ACCEPT_GUID.as_bytes());
+    STANDARD.encode(sha1.digest().bytes())
+}
+
+//reads a whole message, the small messages of the page are never split in fragments
+pub fn read_message(reader: &mut impl Read) -> io::Result<Message> {
+    let mut header = [0; 2];
+    reader.read_exact(&mut header)?;
+    let fin = header[0] & 0x80 != 0;
+    let opcode = header[0] & 0x0F;
+    let masked = header[1] & 0x80 != 0;
+    let len = match header[1] & 0x7F {
+        126 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_be_bytes(len) as u64
+        }
+        127 => {
+            let mut len = [0; 8];
+            reader.read_exact(&mut len)?;
+            u64::from_be_bytes(len)
+        }
+        len => len as u64,
+    };
+    if len > MAX_PAYLOAD {
+        return Err(invalid_data(format!("message of {len} bytes is too big")));
+    }
+    let mut mask = [0; 4];
+    if masked {
+        reader.read_exact(&mut mask)?;
+    }
+    let mut payload = vec![0; len as usize];
+    reader.read_exact(&mut payload)?;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+
+    match opcode {
+        TEXT if fin => String::from_utf8(payload)
+            .map(Message::Text)
+            .map_err(|e| invalid_data(e.to_string())),
+        CLOSE => Ok(Message::Close),
+        PING => Ok(Message::Ping(payload)),
+        PONG => Ok(Message::Pong(payload)),
+        _ => Err(invalid_data(format!(
+            "unsupported frame with opcode {opcode:#x}"
+        ))),
+    }
+}
+
+//the frames of the server are never masked
+pub fn write_message(writer: &mut impl Write, message: &Message) -> io::Result<()> {
+    let (opcode, payload) = match message {
+        Message::Text(text) => (TEXT, text.as_bytes()),
+        Message::Ping(payload) => (PING, payload.as_slice()),
+        Message::Pong(payload) => (PONG, payload.as_slice()),
+        Message::Close => (CLOSE, &[][..]),
+    };
+    let mut frame = vec![0x80 | opcode];
+    match payload.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend((len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend((len as u64).to_be_bytes());
+        }
+    }
+    frame.extend(payload);
+    writer.write_all(&frame)?;
+    writer.flush()
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}